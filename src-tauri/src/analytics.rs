@@ -0,0 +1,260 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::history::StatusEntry;
+use crate::provider::UnifiedDeployment;
+use crate::tracker::DeploymentPhase;
+
+// Slowest builds listed per project
+const SLOWEST_COUNT: usize = 3;
+
+// Account and project (or name, when the provider has no project ID)
+type ProjectKey = (Option<String>, String);
+
+/// One finished build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildSample {
+    pub deployment_id: String,
+    pub created_at: Option<i64>,
+    pub branch: Option<String>,
+    pub commit_message: Option<String>,
+    pub queue_ms: Option<i64>,
+    pub build_ms: i64,
+}
+
+/// Build times of one project over its last few successful builds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBuildStats {
+    pub provider: String,
+    pub account_id: Option<String>,
+    pub project_id: Option<String>,
+    pub name: String,
+    pub builds: usize,
+    pub p50_build_ms: i64,
+    pub p90_build_ms: i64,
+    pub p50_queue_ms: Option<i64>,
+    // Median of the newer half over the older half, above 1.0 when builds are getting slower
+    pub trend: Option<f64>,
+    // Oldest first
+    pub recent: Vec<BuildSample>,
+    // Slowest first
+    pub slowest: Vec<BuildSample>,
+}
+
+/// Per-project build statistics over the last `window` successful builds.
+///
+/// Providers that don't report build times fall back to the time between
+/// the statuses `status_history` returns, so those are only as precise as
+/// the polling interval.
+pub fn build_stats(
+    deployments: Vec<UnifiedDeployment>,
+    status_history: impl Fn(&str) -> Vec<StatusEntry>,
+    window: usize,
+) -> Vec<ProjectBuildStats> {
+    let mut projects: HashMap<ProjectKey, Vec<(UnifiedDeployment, i64)>> = HashMap::new();
+
+    for d in deployments {
+        // Failed and canceled builds stop early and would skew the numbers
+        if DeploymentPhase::from_status(&d.status) != DeploymentPhase::Ready {
+            continue;
+        }
+        let build_ms = match d.build_ms.or_else(|| observed_build_ms(&status_history(&d.id))) {
+            Some(ms) => ms,
+            None => continue,
+        };
+
+        let key = (d.account_id.clone(), d.project_id.clone().unwrap_or_else(|| d.name.clone()));
+        projects.entry(key).or_default().push((d, build_ms));
+    }
+
+    let mut stats: Vec<ProjectBuildStats> = projects
+        .into_values()
+        .map(|builds| project_stats(builds, window))
+        .collect();
+
+    // Projects getting slower first
+    stats.sort_by(|a, b| b.trend.unwrap_or(0.0).total_cmp(&a.trend.unwrap_or(0.0)));
+    stats
+}
+
+fn project_stats(mut builds: Vec<(UnifiedDeployment, i64)>, window: usize) -> ProjectBuildStats {
+    // Newest `window` builds, oldest first
+    builds.sort_by_key(|(d, _)| Reverse(d.created_at.unwrap_or(0)));
+    builds.truncate(window.max(1));
+    builds.reverse();
+
+    let (first, _) = &builds[0];
+    let (provider, account_id, project_id, name) =
+        (first.provider.clone(), first.account_id.clone(), first.project_id.clone(), first.name.clone());
+
+    let recent: Vec<BuildSample> = builds
+        .into_iter()
+        .map(|(d, build_ms)| BuildSample {
+            deployment_id: d.id,
+            created_at: d.created_at,
+            branch: d.branch,
+            commit_message: d.commit_message,
+            queue_ms: d.queue_ms,
+            build_ms,
+        })
+        .collect();
+
+    let durations: Vec<i64> = recent.iter().map(|s| s.build_ms).collect();
+    let queue_times: Vec<i64> = recent.iter().filter_map(|s| s.queue_ms).collect();
+
+    // Compare halves once there are a couple of builds on each side
+    let trend = if durations.len() >= 4 {
+        let (older, newer) = durations.split_at(durations.len() / 2);
+        let older = percentile(older, 50.0);
+        let newer = percentile(newer, 50.0);
+        (older > 0).then(|| newer as f64 / older as f64)
+    } else {
+        None
+    };
+
+    let mut slowest = recent.clone();
+    slowest.sort_by_key(|s| Reverse(s.build_ms));
+    slowest.truncate(SLOWEST_COUNT);
+
+    ProjectBuildStats {
+        provider,
+        account_id,
+        project_id,
+        name,
+        builds: recent.len(),
+        p50_build_ms: percentile(&durations, 50.0),
+        p90_build_ms: percentile(&durations, 90.0),
+        p50_queue_ms: (!queue_times.is_empty()).then(|| percentile(&queue_times, 50.0)),
+        trend,
+        recent,
+        slowest,
+    }
+}
+
+// Nearest-rank percentile, 0 for no values
+fn percentile(values: &[i64], p: f64) -> i64 {
+    if values.is_empty() {
+        return 0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// From first being seen building to first being seen finished
+fn observed_build_ms(statuses: &[StatusEntry]) -> Option<i64> {
+    let started = statuses
+        .iter()
+        .find(|s| DeploymentPhase::from_status(&s.status) == DeploymentPhase::Building)?;
+    let finished = statuses
+        .iter()
+        .find(|s| s.at > started.at && DeploymentPhase::from_status(&s.status) == DeploymentPhase::Ready)?;
+    Some(finished.at - started.at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(id: &str, project: &str, status: &str, created_at: i64, build_ms: Option<i64>) -> UnifiedDeployment {
+        UnifiedDeployment {
+            id: id.to_string(),
+            name: project.to_string(),
+            status: status.to_string(),
+            created_at: Some(created_at),
+            project_id: Some(project.to_string()),
+            account_id: Some("account".to_string()),
+            build_ms,
+            ..UnifiedDeployment::test_default()
+        }
+    }
+
+    // Ready builds of one project, oldest first, with these durations
+    fn builds(project: &str, durations: &[i64]) -> Vec<UnifiedDeployment> {
+        durations
+            .iter()
+            .enumerate()
+            .map(|(i, ms)| deployment(&format!("{}-{}", project, i), project, "READY", i as i64 * 1000, Some(*ms)))
+            .collect()
+    }
+
+    #[test]
+    fn percentile_of_no_samples_is_zero() {
+        assert_eq!(percentile(&[], 50.0), 0);
+        assert_eq!(percentile(&[], 90.0), 0);
+    }
+
+    #[test]
+    fn percentile_of_one_sample_is_that_sample() {
+        assert_eq!(percentile(&[42], 0.0), 42);
+        assert_eq!(percentile(&[42], 50.0), 42);
+        assert_eq!(percentile(&[42], 100.0), 42);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let values = [50, 10, 40, 20, 30, 100, 90, 80, 70, 60];
+        assert_eq!(percentile(&values, 0.0), 10);
+        assert_eq!(percentile(&values, 50.0), 50);
+        assert_eq!(percentile(&values, 90.0), 90);
+        assert_eq!(percentile(&values, 95.0), 100);
+        assert_eq!(percentile(&values, 100.0), 100);
+        assert_eq!(percentile(&[1, 2, 3], 50.0), 2);
+    }
+
+    #[test]
+    fn trend_compares_newer_half_to_older_half() {
+        let stats = build_stats(builds("app", &[100, 100, 200, 200]), |_| Vec::new(), 20);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].trend, Some(2.0));
+        assert_eq!(stats[0].p50_build_ms, 100);
+        assert_eq!(stats[0].p90_build_ms, 200);
+    }
+
+    #[test]
+    fn trend_needs_two_builds_per_half() {
+        let stats = build_stats(builds("app", &[100, 100, 200]), |_| Vec::new(), 20);
+        assert_eq!(stats[0].trend, None);
+    }
+
+    #[test]
+    fn slowing_projects_sort_first() {
+        let mut deployments = builds("steady", &[100, 100, 100, 100]);
+        deployments.extend(builds("slower", &[100, 100, 300, 300]));
+        deployments.extend(builds("faster", &[300, 300, 100, 100]));
+
+        let stats = build_stats(deployments, |_| Vec::new(), 20);
+        let names: Vec<&str> = stats.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["slower", "steady", "faster"]);
+    }
+
+    #[test]
+    fn only_the_newest_window_of_ready_builds_counts() {
+        let mut deployments = builds("app", &[900, 100, 100, 100]);
+        deployments.push(deployment("failed", "app", "ERROR", 10_000, Some(5)));
+
+        let stats = build_stats(deployments, |_| Vec::new(), 3);
+        assert_eq!(stats[0].builds, 3);
+        assert_eq!(stats[0].p90_build_ms, 100);
+        assert_eq!(stats[0].recent.first().map(|s| s.deployment_id.as_str()), Some("app-1"));
+    }
+
+    #[test]
+    fn missing_build_times_come_from_status_history() {
+        let deployments = vec![deployment("observed", "app", "READY", 0, None)];
+        let history = |_: &str| {
+            vec![
+                StatusEntry { status: "QUEUED".to_string(), at: 0 },
+                StatusEntry { status: "BUILDING".to_string(), at: 1_000 },
+                StatusEntry { status: "BUILDING".to_string(), at: 5_000 },
+                StatusEntry { status: "READY".to_string(), at: 31_000 },
+            ]
+        };
+
+        let stats = build_stats(deployments, history, 20);
+        assert_eq!(stats[0].p50_build_ms, 30_000);
+    }
+}
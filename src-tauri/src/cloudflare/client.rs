@@ -0,0 +1,175 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
+use crate::provider;
+use crate::vercel::LogLine;
+use super::types::*;
+
+const API_BASE: &str = "https://api.cloudflare.com/client/v4";
+// Projects polled per account; each costs one deployments request per poll
+const MAX_PROJECTS: usize = 20;
+pub struct CloudflareClient {
+    client: reqwest::Client,
+    token: String,
+    account_id: String,
+    quota: Quota,
+}
+
+impl CloudflareClient {
+    pub fn new(token: String, account_id: String) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token))?,
+        );
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self { client, token, account_id, quota: Quota::default() })
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        let resp = self
+            .client
+            .get(format!("{}{}", API_BASE, path))
+            .query(query)
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Cloudflare request failed", resp).await.into());
+        }
+
+        let response: ApiResponse<T> = resp.json().await?;
+
+        if !response.success {
+            let message = response.errors.iter().map(|e| e.message.clone()).collect::<Vec<_>>().join(", ");
+            return Err(AppError::Provider {
+                code: response.errors.first().map(|e| e.code.to_string()),
+                message: format!("Cloudflare request failed: {}", message),
+            }
+            .into());
+        }
+
+        response.result.ok_or_else(|| anyhow!("No result in Cloudflare response"))
+    }
+
+    pub async fn verify_token(&self) -> Result<TokenVerification> {
+        self.get("/user/tokens/verify", &[]).await
+    }
+
+    pub async fn get_account(&self) -> Result<CloudflareAccount> {
+        self.get(&format!("/accounts/{}", self.account_id), &[]).await
+    }
+
+    pub async fn list_projects(&self) -> Result<Vec<PagesProject>> {
+        self.get(&format!("/accounts/{}/pages/projects", self.account_id), &[]).await
+    }
+
+    pub async fn list_deployments(&self, project_name: &str, limit: u32) -> Result<Vec<PagesDeployment>> {
+        self.get(
+            &format!("/accounts/{}/pages/projects/{}/deployments", self.account_id, project_name),
+            &[("per_page", limit.to_string())],
+        )
+        .await
+    }
+
+    /// Latest deployments across the `MAX_PROJECTS` most recently deployed
+    /// Pages projects
+    pub async fn list_all_deployments(&self, limit: u32) -> Result<Vec<PagesDeployment>> {
+        let mut projects = self.list_projects().await?;
+        projects.sort_by(|a, b| b.last_active().cmp(a.last_active()));
+        projects.truncate(MAX_PROJECTS);
+
+        // Fetch each project's deployments concurrently
+        let results = futures::future::join_all(
+            projects.iter().map(|project| self.list_deployments(&project.name, limit)),
+        )
+        .await;
+
+        let mut all_deployments = provider::merge_listings("CLOUDFLARE", results)?;
+
+        // Sort by created_on descending (most recent first)
+        all_deployments.sort_by(|a, b| {
+            let a_time = a.created_on.as_deref().unwrap_or("");
+            let b_time = b.created_on.as_deref().unwrap_or("");
+            b_time.cmp(a_time)
+        });
+
+        all_deployments.truncate(limit as usize);
+
+        Ok(all_deployments)
+    }
+
+    pub async fn get_deployment(&self, project_name: &str, deployment_id: &str) -> Result<PagesDeployment> {
+        self.get(
+            &format!("/accounts/{}/pages/projects/{}/deployments/{}", self.account_id, project_name, deployment_id),
+            &[],
+        )
+        .await
+    }
+
+    /// Look up a deployment without knowing its project.
+    ///
+    /// Pages only serves deployments under their project, so every project
+    /// is asked at once. Only needed for IDs that don't carry their project.
+    pub async fn find_deployment(&self, deployment_id: &str) -> Result<PagesDeployment> {
+        let projects = self.list_projects().await?;
+
+        let results = futures::future::join_all(
+            projects.iter().map(|project| self.get_deployment(&project.name, deployment_id)),
+        )
+        .await;
+
+        results
+            .into_iter()
+            .find_map(|r| r.ok())
+            .ok_or_else(|| anyhow!("Deployment {} not found", deployment_id))
+    }
+
+    pub async fn get_deployment_logs(&self, project_name: &str, deployment_id: &str) -> Result<Vec<LogLine>> {
+        let logs: DeploymentLogs = self
+            .get(
+                &format!(
+                    "/accounts/{}/pages/projects/{}/deployments/{}/history/logs",
+                    self.account_id, project_name, deployment_id
+                ),
+                &[],
+            )
+            .await?;
+
+        Ok(logs
+            .data
+            .into_iter()
+            .map(|entry| {
+                let lower = entry.line.to_lowercase();
+                LogLine {
+                    timestamp: provider::parse_timestamp(entry.ts.as_deref()).unwrap_or(0),
+                    is_error: lower.contains("error") || lower.contains("failed"),
+                    text: entry.line,
+                }
+            })
+            .collect())
+    }
+}
+
+pub fn create_client(token: &str, account_id: &str) -> Result<CloudflareClient> {
+    CloudflareClient::new(token.to_string(), account_id.to_string())
+}
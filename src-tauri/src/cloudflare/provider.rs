@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crate::provider::{self, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use crate::vercel::LogLine;
+use super::client::{create_client, CloudflareClient};
+use super::types::*;
+
+impl From<PagesDeployment> for UnifiedDeployment {
+    fn from(d: PagesDeployment) -> Self {
+        let metadata = d.deployment_trigger.as_ref().and_then(|t| t.metadata.as_ref());
+
+        UnifiedDeployment {
+            // Deployment ids carry their project ("project/deployment") so
+            // lookups can go straight to it
+            id: format!("{}/{}", d.project_name, d.id),
+            provider: "cloudflare".to_string(),
+            name: d.project_name.clone(),
+            url: d.url.clone(),
+            status: d.status().to_string(),
+            created_at: provider::parse_timestamp(d.created_on.as_deref()),
+            commit_message: metadata.and_then(|m| m.commit_message.clone()),
+            branch: metadata.and_then(|m| m.branch.clone()),
+            project_id: Some(d.project_name.clone()),
+            service_id: None,
+            git_author_login: None,  // Pages doesn't expose git author
+            team_slug: None,  // Not used for Cloudflare
+            account_id: None,
+            queue_ms: queue_ms(&d.stages),
+            build_ms: build_ms(&d.stages),
+            environment: d.environment.clone(),
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+fn stage_time(value: &Option<String>) -> Option<i64> {
+    provider::parse_timestamp(value.as_deref())
+}
+
+// Time spent in the `queued` stage
+fn queue_ms(stages: &[DeploymentStage]) -> Option<i64> {
+    let queued = stages.iter().find(|s| s.name == StageName::Queued)?;
+    provider::duration_ms(stage_time(&queued.started_on), stage_time(&queued.ended_on))
+}
+
+// From the first stage after the queue starting to the last one ending
+fn build_ms(stages: &[DeploymentStage]) -> Option<i64> {
+    let mut work = stages.iter().filter(|s| s.name != StageName::Queued);
+    let start = work.next().and_then(|s| stage_time(&s.started_on));
+    let end = stages.last().filter(|s| s.name != StageName::Queued).and_then(|s| stage_time(&s.ended_on));
+    provider::duration_ms(start, end)
+}
+
+#[async_trait]
+impl DeploymentProvider for CloudflareClient {
+    async fn validate_token(&self) -> Result<()> {
+        let verification = self.verify_token().await?;
+        if verification.status != "active" {
+            return Err(anyhow!("Token is {}", verification.status));
+        }
+        Ok(())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let projects = CloudflareClient::list_projects(self).await?;
+        Ok(projects
+            .into_iter()
+            .map(|p| UnifiedProject {
+                updated_at: provider::parse_timestamp(p.created_on.as_deref()),
+                id: p.id,
+                provider: "cloudflare".to_string(),
+                name: p.name,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let deployments = CloudflareClient::list_all_deployments(self, limit).await?;
+        Ok(deployments.into_iter().map(UnifiedDeployment::from).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let deployment = match deployment_id.split_once('/') {
+            Some((project_name, id)) => CloudflareClient::get_deployment(self, project_name, id).await?,
+            None => self.find_deployment(deployment_id).await?,
+        };
+        Ok(deployment.into())
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        match deployment_id.split_once('/') {
+            Some((project_name, id)) => self.get_deployment_logs(project_name, id).await,
+            None => {
+                let deployment = self.find_deployment(deployment_id).await?;
+                self.get_deployment_logs(&deployment.project_name, deployment_id).await
+            }
+        }
+    }
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    let account_id = account
+        .provider_account_id
+        .as_deref()
+        .ok_or_else(|| anyhow!("Cloudflare account {} has no account ID", account.id))?;
+    Ok(Box::new(create_client(&account.token, account_id)?.with_quota(account.quota.clone())))
+}
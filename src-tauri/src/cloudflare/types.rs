@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+// Every Cloudflare API response is wrapped in this envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    #[serde(default)]
+    pub errors: Vec<ApiError>,
+    pub result: Option<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenVerification {
+    pub id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudflareAccount {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagesProject {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub subdomain: Option<String>,
+    #[serde(default)]
+    pub production_branch: Option<String>,
+    #[serde(default)]
+    pub created_on: Option<String>,
+    #[serde(default)]
+    pub latest_deployment: Option<LatestDeployment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatestDeployment {
+    #[serde(default)]
+    pub created_on: Option<String>,
+}
+
+impl PagesProject {
+    /// When the project last deployed, falling back to its creation
+    pub fn last_active(&self) -> &str {
+        self.latest_deployment
+            .as_ref()
+            .and_then(|d| d.created_on.as_deref())
+            .or(self.created_on.as_deref())
+            .unwrap_or("")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StageName {
+    Queued,
+    Initialize,
+    CloneRepo,
+    Build,
+    Deploy,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Idle,
+    Active,
+    Success,
+    Failure,
+    Canceled,
+    Skipped,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentStage {
+    pub name: StageName,
+    pub status: StageStatus,
+    #[serde(default)]
+    pub started_on: Option<String>,
+    #[serde(default)]
+    pub ended_on: Option<String>,
+}
+
+impl DeploymentStage {
+    /// Convert the current stage to unified status string for frontend
+    pub fn to_unified(&self) -> &'static str {
+        match (&self.name, &self.status) {
+            (_, StageStatus::Failure) => "ERROR",
+            (_, StageStatus::Canceled) => "CANCELED",
+            (StageName::Deploy, StageStatus::Success) => "READY",
+            (StageName::Queued, _) => "QUEUED",
+            (StageName::Unknown, _) => "UNKNOWN",
+            _ => "BUILDING",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagesDeployment {
+    pub id: String,
+    #[serde(default)]
+    pub short_id: Option<String>,
+    pub project_name: String,
+    /// "production" or "preview"
+    #[serde(default)]
+    pub environment: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub created_on: Option<String>,
+    #[serde(default)]
+    pub modified_on: Option<String>,
+    #[serde(default)]
+    pub latest_stage: Option<DeploymentStage>,
+    #[serde(default)]
+    pub stages: Vec<DeploymentStage>,
+    #[serde(default)]
+    pub deployment_trigger: Option<DeploymentTrigger>,
+    #[serde(default)]
+    pub is_skipped_build: bool,
+}
+
+impl PagesDeployment {
+    pub fn status(&self) -> &'static str {
+        if self.is_skipped_build {
+            return "CANCELED";
+        }
+        self.latest_stage
+            .as_ref()
+            .map(|s| s.to_unified())
+            .unwrap_or("UNKNOWN")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentTrigger {
+    #[serde(rename = "type", default)]
+    pub trigger_type: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<TriggerMetadata>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerMetadata {
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub commit_hash: Option<String>,
+    #[serde(default)]
+    pub commit_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentLogs {
+    #[serde(default)]
+    pub total: Option<i64>,
+    #[serde(default)]
+    pub data: Vec<DeploymentLogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentLogEntry {
+    #[serde(default)]
+    pub ts: Option<String>,
+    pub line: String,
+}
@@ -1,9 +1,18 @@
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, State};
-use crate::vercel::{self, User};
-use crate::railway;
-use crate::state::{AppState, CachedAccount};
+use crate::vercel::{self, User, VercelClient};
+use crate::cloudflare;
+use crate::error::AppError;
+use crate::fly;
+use crate::http::Quota;
+use crate::integrations::Integration;
+use crate::netlify;
+use crate::railway::{self, RailwayClient};
+use crate::render;
+use crate::rules::NotificationSettings;
+use crate::state::{AppState, CachedAccount, TeamScope};
+use crate::webhook::WebhookConfig;
 
 const SERVICE_NAME: &str = "vercel-menubar";
 const KEYCHAIN_KEY: &str = "app-data"; // Single keychain entry for all data
@@ -18,8 +27,12 @@ pub struct Account {
     pub scope_type: String,
     pub team_name: Option<String>,
     pub team_slug: Option<String>,
+    #[serde(default)]
+    pub team_id: Option<String>,
     #[serde(default = "default_provider")]
     pub provider: String,
+    #[serde(default)]
+    pub included_teams: Vec<TeamScope>,
 }
 
 fn default_provider() -> String {
@@ -36,7 +49,9 @@ impl From<CachedAccount> for Account {
             scope_type: c.scope_type,
             team_name: c.team_name,
             team_slug: c.team_slug,
+            team_id: c.team_id,
             provider: c.provider,
+            included_teams: c.included_teams,
         }
     }
 }
@@ -47,6 +62,12 @@ impl From<CachedAccount> for Account {
 struct KeychainData {
     accounts: Vec<StoredAccount>,
     active_account_id: Option<String>,
+    #[serde(default)]
+    webhook: WebhookConfig,
+    #[serde(default)]
+    integrations: Vec<Integration>,
+    #[serde(default)]
+    notifications: NotificationSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,40 +80,46 @@ struct StoredAccount {
     scope_type: String,
     team_name: Option<String>,
     team_slug: Option<String>,
+    #[serde(default)]
+    team_id: Option<String>,
     token: String,
     #[serde(default = "default_provider")]
     provider: String,
+    #[serde(default)]
+    provider_account_id: Option<String>,
+    #[serde(default)]
+    included_teams: Vec<TeamScope>,
 }
 
-fn get_keychain_entry() -> Result<Entry, String> {
+fn get_keychain_entry() -> Result<Entry, AppError> {
     Entry::new(SERVICE_NAME, KEYCHAIN_KEY)
-        .map_err(|e| format!("Failed to create keyring entry: {}", e))
+        .map_err(|e| AppError::keychain(format!("Failed to create keyring entry: {}", e)))
 }
 
 // Load all data from keychain in ONE read
-fn load_keychain_data() -> Result<KeychainData, String> {
+fn load_keychain_data() -> Result<KeychainData, AppError> {
     let entry = get_keychain_entry()?;
     match entry.get_password() {
         Ok(data) => {
             serde_json::from_str(&data)
-                .map_err(|e| format!("Failed to parse keychain data: {}", e))
+                .map_err(|e| AppError::keychain(format!("Failed to parse keychain data: {}", e)))
         }
         Err(keyring::Error::NoEntry) => Ok(KeychainData::default()),
-        Err(e) => Err(format!("Failed to get keychain data: {}", e)),
+        Err(e) => Err(AppError::keychain(format!("Failed to get keychain data: {}", e))),
     }
 }
 
 // Save all data to keychain in ONE write
-fn save_keychain_data(data: &KeychainData) -> Result<(), String> {
+fn save_keychain_data(data: &KeychainData) -> Result<(), AppError> {
     let entry = get_keychain_entry()?;
     let json = serde_json::to_string(data)
-        .map_err(|e| format!("Failed to serialize keychain data: {}", e))?;
+        .map_err(|e| AppError::keychain(format!("Failed to serialize keychain data: {}", e)))?;
     entry.set_password(&json)
-        .map_err(|e| format!("Failed to save keychain data: {}", e))
+        .map_err(|e| AppError::keychain(format!("Failed to save keychain data: {}", e)))
 }
 
 // Initialize state from keychain - only called once
-pub async fn initialize_state(state: &AppState) -> Result<(), String> {
+pub async fn initialize_state(state: &AppState) -> Result<(), AppError> {
     if state.is_initialized() {
         return Ok(());
     }
@@ -102,6 +129,9 @@ pub async fn initialize_state(state: &AppState) -> Result<(), String> {
 
     // Set active account
     state.set_active_account_id(keychain_data.active_account_id);
+    state.webhook.set_config(keychain_data.webhook);
+    state.integrations.set(keychain_data.integrations);
+    state.rules.set(keychain_data.notifications);
 
     // Load each account into cache
     for stored in keychain_data.accounts {
@@ -114,17 +144,53 @@ pub async fn initialize_state(state: &AppState) -> Result<(), String> {
             scope_type: stored.scope_type,
             team_name: stored.team_name,
             team_slug: stored.team_slug,
+            team_id: stored.team_id,
             token: stored.token,
             provider: stored.provider,
+            provider_account_id: stored.provider_account_id,
+            included_teams: stored.included_teams,
+            quota: Quota::default(),
         });
     }
 
     state.set_initialized(true);
+
+    backfill_team_ids(state).await
+}
+
+// Team tokens saved before team IDs were stored only have the team's name
+// and slug; look the ID up once and persist it
+async fn backfill_team_ids(state: &AppState) -> Result<(), AppError> {
+    let mut updated = false;
+
+    for mut account in state.get_all_accounts() {
+        if account.provider != "vercel" || account.scope_type != "team" || account.team_id.is_some() {
+            continue;
+        }
+
+        let client = match vercel::create_client(&account.token).map(|c| c.with_quota(account.quota.clone())) {
+            Ok(client) => client,
+            Err(_) => continue,
+        };
+        match client.get_token_info().await {
+            Ok(info) if info.team_id.is_some() => {
+                account.team_id = info.team_id;
+                state.set_account(account);
+                updated = true;
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[AUTH] Failed to look up team of {}: {}", account.id, e),
+        }
+    }
+
+    if updated {
+        save_state_to_keychain(state)?;
+    }
     Ok(())
 }
 
 // Save current state to keychain
-fn save_state_to_keychain(state: &AppState) -> Result<(), String> {
+pub(crate) fn save_state_to_keychain(state: &AppState) -> Result<(), AppError> {
     let accounts = state.get_all_accounts();
     let stored_accounts: Vec<StoredAccount> = accounts.into_iter().map(|a| StoredAccount {
         id: a.id,
@@ -134,66 +200,69 @@ fn save_state_to_keychain(state: &AppState) -> Result<(), String> {
         scope_type: a.scope_type,
         team_name: a.team_name,
         team_slug: a.team_slug,
+        team_id: a.team_id,
         token: a.token,
         provider: a.provider,
+        provider_account_id: a.provider_account_id,
+        included_teams: a.included_teams,
     }).collect();
 
     let data = KeychainData {
         accounts: stored_accounts,
         active_account_id: state.get_active_account_id(),
+        webhook: state.webhook.config(),
+        integrations: state.integrations.list(),
+        notifications: state.rules.get(),
     };
 
     save_keychain_data(&data)
 }
 
+// Cache a validated account, persist it and start polling it
+fn register_account(state: &AppState, cached: CachedAccount) -> Result<Account, AppError> {
+    state.set_token(&cached.id, &cached.token);
+    state.set_account(cached.clone());
+
+    // Save to keychain (single write)
+    save_state_to_keychain(state)?;
+
+    // Start polling the new account
+    state.scheduler.sync(&state.get_all_accounts());
+
+    Ok(Account::from(cached))
+}
+
 #[tauri::command]
-pub async fn add_account(token: String, state: State<'_, AppState>) -> Result<Account, String> {
+pub async fn add_account(token: String, state: State<'_, AppState>) -> Result<Account, AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
     // Validate token by making API call
     let client = vercel::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     let user = client
         .get_user()
         .await
-        .map_err(|e| format!("Invalid token: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Invalid token"))?;
 
     // Get token info to determine scope
-    let (scope_type, team_name, team_slug) = match client.get_token_info().await {
+    let (scope_type, team_name, team_slug, team_id) = match client.get_token_info().await {
         Ok(token_info) => {
             if let Some(team_id) = token_info.team_id {
                 let team_info = client.get_team(&team_id).await.ok();
                 let team_name = team_info.as_ref().map(|t| t.name.clone());
                 let team_slug = team_info.as_ref().map(|t| t.slug.clone());
-                ("team".to_string(), team_name, team_slug)
+                ("team".to_string(), team_name, team_slug, Some(team_id))
             } else {
-                ("user".to_string(), None, None)
+                ("user".to_string(), None, None, None)
             }
         }
-        Err(_) => ("user".to_string(), None, None),
+        Err(_) => ("user".to_string(), None, None, None),
     };
 
     // Cache in memory
     let cached = CachedAccount {
-        id: user.id.clone(),
-        username: user.username.clone(),
-        email: user.email.clone(),
-        name: user.name.clone(),
-        scope_type: scope_type.clone(),
-        team_name: team_name.clone(),
-        team_slug: team_slug.clone(),
-        token,
-        provider: "vercel".to_string(),
-    };
-    state.set_account(cached.clone());
-    state.set_token(&user.id, &cached.token);
-
-    // Save to keychain (single write)
-    save_state_to_keychain(&state)?;
-
-    Ok(Account {
         id: user.id,
         username: user.username,
         email: user.email,
@@ -201,12 +270,18 @@ pub async fn add_account(token: String, state: State<'_, AppState>) -> Result<Ac
         scope_type,
         team_name,
         team_slug,
+        team_id,
+        token,
         provider: "vercel".to_string(),
-    })
+        provider_account_id: None,
+        included_teams: Vec::new(),
+        quota: Quota::default(),
+    };
+    register_account(&state, cached)
 }
 
 #[tauri::command]
-pub async fn list_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, String> {
+pub async fn list_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -215,7 +290,7 @@ pub async fn list_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, S
 }
 
 #[tauri::command]
-pub async fn get_account_token(account_id: String, state: State<'_, AppState>) -> Result<Option<String>, String> {
+pub async fn get_account_token(account_id: String, state: State<'_, AppState>) -> Result<Option<String>, AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -224,13 +299,19 @@ pub async fn get_account_token(account_id: String, state: State<'_, AppState>) -
 }
 
 #[tauri::command]
-pub async fn remove_account(account_id: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn remove_account(account_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
     // Remove from cache
     state.remove_token(&account_id);
 
+    // Stop polling the removed account before forgetting its deployments,
+    // so an in-flight poll can't record them again
+    state.scheduler.sync(&state.get_all_accounts());
+    state.tracker.forget_account(&account_id);
+    state.history.forget_account(&account_id);
+
     // Save to keychain (single write)
     save_state_to_keychain(&state)?;
 
@@ -238,7 +319,7 @@ pub async fn remove_account(account_id: String, state: State<'_, AppState>) -> R
 }
 
 #[tauri::command]
-pub async fn rename_account(account_id: String, new_name: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn rename_account(account_id: String, new_name: String, state: State<'_, AppState>) -> Result<(), AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -252,7 +333,7 @@ pub async fn rename_account(account_id: String, new_name: String, state: State<'
 }
 
 #[tauri::command]
-pub async fn get_active_account(state: State<'_, AppState>) -> Result<Option<String>, String> {
+pub async fn get_active_account(state: State<'_, AppState>) -> Result<Option<String>, AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -260,7 +341,7 @@ pub async fn get_active_account(state: State<'_, AppState>) -> Result<Option<Str
 }
 
 #[tauri::command]
-pub async fn set_active_account(account_id: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn set_active_account(account_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -275,7 +356,7 @@ pub async fn set_active_account(account_id: String, state: State<'_, AppState>)
 
 // Legacy commands for compatibility
 #[tauri::command]
-pub async fn save_token(token: String, state: State<'_, AppState>) -> Result<User, String> {
+pub async fn save_token(token: String, state: State<'_, AppState>) -> Result<User, AppError> {
     let account = add_account(token, state.clone()).await?;
     set_active_account(account.id.clone(), state).await?;
 
@@ -288,7 +369,7 @@ pub async fn save_token(token: String, state: State<'_, AppState>) -> Result<Use
 }
 
 #[tauri::command]
-pub async fn get_stored_token(state: State<'_, AppState>) -> Result<Option<String>, String> {
+pub async fn get_stored_token(state: State<'_, AppState>) -> Result<Option<String>, AppError> {
     let active_id = get_active_account(state.clone()).await?;
     match active_id {
         Some(id) => get_account_token(id, state).await,
@@ -296,8 +377,127 @@ pub async fn get_stored_token(state: State<'_, AppState>) -> Result<Option<Strin
     }
 }
 
+// Given account, or the active account
+async fn resolve_account(state: &AppState, account_id: Option<String>) -> Result<CachedAccount, AppError> {
+    initialize_state(state).await?;
+
+    let account_id = match account_id {
+        Some(id) => id,
+        None => state.get_active_account_id().ok_or_else(|| AppError::unauthorized("Not authenticated"))?,
+    };
+    state.get_account(&account_id).ok_or_else(|| AppError::not_found("Account not found"))
+}
+
+/// Vercel client for an account, or the active account, scoped to its team
+pub async fn get_vercel_client(state: &AppState, account_id: Option<String>) -> Result<VercelClient, AppError> {
+    let account = resolve_account(state, account_id).await?;
+
+    vercel::create_client_for_account(&account)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))
+}
+
+/// Vercel client scoped to whichever included team owns the deployment
+pub async fn get_vercel_deployment_client(
+    state: &AppState,
+    account_id: Option<String>,
+    deployment_id: &str,
+) -> Result<VercelClient, AppError> {
+    let account = resolve_account(state, account_id).await?;
+    let team_slug = state.history.get(deployment_id).and_then(|d| d.team_slug);
+
+    vercel::find_deployment_client(&account, deployment_id, team_slug.as_deref())
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to find deployment"))
+}
+
+/// Railway client for an account, or the active account, using the header
+/// its token type needs
+pub async fn get_railway_client(state: &AppState, account_id: Option<String>) -> Result<RailwayClient, AppError> {
+    let account = resolve_account(state, account_id).await?;
+    if account.provider != "railway" {
+        return Err(AppError::provider("Not a Railway account"));
+    }
+
+    railway::create_client_for_account(&account)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))
+}
+
+/// A team reachable with a Vercel account's token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VercelTeamOption {
+    pub id: String,
+    pub slug: String,
+    pub name: String,
+    pub included: bool,
+}
+
+#[tauri::command]
+pub async fn list_vercel_teams(account_id: String, state: State<'_, AppState>) -> Result<Vec<VercelTeamOption>, AppError> {
+    let account = resolve_account(&state, Some(account_id)).await?;
+    if account.provider != "vercel" {
+        return Err(AppError::provider("Teams are only available for Vercel accounts"));
+    }
+
+    let client = vercel::create_client(&account.token)
+        .map(|c| c.with_quota(account.quota.clone()))
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let teams = client
+        .list_teams()
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to list teams"))?;
+
+    Ok(teams
+        .into_iter()
+        .map(|t| VercelTeamOption {
+            included: account.included_teams.iter().any(|i| i.id == t.id),
+            id: t.id,
+            slug: t.slug,
+            name: t.name,
+        })
+        .collect())
+}
+
 #[tauri::command]
-pub async fn validate_stored_token(state: State<'_, AppState>) -> Result<Option<User>, String> {
+pub async fn set_vercel_teams(account_id: String, team_ids: Vec<String>, state: State<'_, AppState>) -> Result<Account, AppError> {
+    let account = resolve_account(&state, Some(account_id.clone())).await?;
+    if account.provider != "vercel" {
+        return Err(AppError::provider("Teams are only available for Vercel accounts"));
+    }
+
+    let client = vercel::create_client(&account.token)
+        .map(|c| c.with_quota(account.quota.clone()))
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let teams = client
+        .list_teams()
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to list teams"))?;
+
+    // Only keep teams the token can still reach
+    let included: Vec<TeamScope> = teams
+        .into_iter()
+        .filter(|t| team_ids.contains(&t.id))
+        .map(|t| TeamScope { id: t.id, slug: t.slug, name: t.name })
+        .collect();
+
+    state.set_included_teams(&account_id, included);
+
+    // Save to keychain (single write)
+    save_state_to_keychain(&state)?;
+
+    // Poll the new selection right away
+    state.scheduler.wake();
+
+    state
+        .get_account(&account_id)
+        .map(Account::from)
+        .ok_or_else(|| AppError::not_found("Account not found"))
+}
+
+#[tauri::command]
+pub async fn validate_stored_token(state: State<'_, AppState>) -> Result<Option<User>, AppError> {
     // Initialize state from keychain (single read)
     initialize_state(&state).await?;
 
@@ -307,7 +507,7 @@ pub async fn validate_stored_token(state: State<'_, AppState>) -> Result<Option<
     };
 
     let client = vercel::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     match client.get_user().await {
         Ok(user) => Ok(Some(user)),
@@ -322,7 +522,7 @@ pub async fn validate_stored_token(state: State<'_, AppState>) -> Result<Option<
 }
 
 #[tauri::command]
-pub async fn delete_token(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn delete_token(state: State<'_, AppState>) -> Result<(), AppError> {
     if let Some(id) = get_active_account(state.clone()).await? {
         remove_account(id, state).await?;
     }
@@ -330,7 +530,7 @@ pub async fn delete_token(state: State<'_, AppState>) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn get_current_account(state: State<'_, AppState>) -> Result<Option<Account>, String> {
+pub async fn get_current_account(state: State<'_, AppState>) -> Result<Option<Account>, AppError> {
     initialize_state(&state).await?;
 
     let active_id = match state.get_active_account_id() {
@@ -355,7 +555,27 @@ pub fn open_railway_tokens(_app: AppHandle) {
 }
 
 #[tauri::command]
-pub async fn add_railway_account(token: String, token_type: String, state: State<'_, AppState>) -> Result<Account, String> {
+pub fn open_netlify_tokens(_app: AppHandle) {
+    let _ = open::that("https://app.netlify.com/user/applications#personal-access-tokens");
+}
+
+#[tauri::command]
+pub fn open_render_tokens(_app: AppHandle) {
+    let _ = open::that("https://dashboard.render.com/u/settings#api-keys");
+}
+
+#[tauri::command]
+pub fn open_fly_tokens(_app: AppHandle) {
+    let _ = open::that("https://fly.io/user/personal_access_tokens");
+}
+
+#[tauri::command]
+pub fn open_cloudflare_tokens(_app: AppHandle) {
+    let _ = open::that("https://dash.cloudflare.com/profile/api-tokens");
+}
+
+#[tauri::command]
+pub async fn add_railway_account(token: String, token_type: String, state: State<'_, AppState>) -> Result<Account, AppError> {
     // Initialize if needed
     initialize_state(&state).await?;
 
@@ -363,7 +583,7 @@ pub async fn add_railway_account(token: String, token_type: String, state: State
 
     // Validate token by making API call with correct header based on token type
     let client = railway::create_client_with_type(&token, &token_type)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     // For workspace tokens, we use Bearer auth and verify by fetching projects
     // For project tokens, we need to use Project-Access-Token header
@@ -372,7 +592,7 @@ pub async fn add_railway_account(token: String, token_type: String, state: State
         let _projects = client
             .list_projects(Some(1))
             .await
-            .map_err(|e| format!("Invalid token: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Invalid token"))?;
 
         // Try to get workspace name
         let workspace_name = match client.get_workspaces().await {
@@ -397,7 +617,7 @@ pub async fn add_railway_account(token: String, token_type: String, state: State
         let projects = client
             .list_projects(Some(1))
             .await
-            .map_err(|e| format!("Invalid token: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Invalid token"))?;
 
         let project_name = projects.first().map(|p| p.name.clone());
         let token_hash = format!("{:x}", md5::compute(&token));
@@ -414,30 +634,210 @@ pub async fn add_railway_account(token: String, token_type: String, state: State
 
     // Cache in memory
     let cached = CachedAccount {
-        id: account_id.clone(),
-        username: username.clone(),
-        email: email.clone(),
-        name: name.clone(),
+        id: account_id,
+        username,
+        email,
+        name,
         scope_type: if is_workspace_token { "workspace".to_string() } else { "project".to_string() },
-        team_name: workspace_name.clone(),
+        team_name: workspace_name,
         team_slug: None,
+        team_id: None,
         token,
         provider: "railway".to_string(),
+        provider_account_id: None,
+        included_teams: Vec::new(),
+        quota: Quota::default(),
     };
-    state.set_account(cached.clone());
-    state.set_token(&account_id, &cached.token);
+    register_account(&state, cached)
+}
 
-    // Save to keychain (single write)
-    save_state_to_keychain(&state)?;
+#[tauri::command]
+pub async fn add_netlify_account(token: String, state: State<'_, AppState>) -> Result<Account, AppError> {
+    // Initialize if needed
+    initialize_state(&state).await?;
+
+    // Validate token by making API call
+    let client = netlify::create_client(&token)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let user = client
+        .get_user()
+        .await
+        .map_err(|e| AppError::from(e).context("Invalid token"))?;
+
+    let email = user.email.clone().unwrap_or_default();
+    let username = user.full_name.clone().unwrap_or_else(|| email.clone());
+
+    // Cache in memory
+    let cached = CachedAccount {
+        id: user.id,
+        username,
+        email,
+        name: user.full_name,
+        scope_type: "user".to_string(),
+        team_name: None,
+        team_slug: None,
+        team_id: None,
+        token,
+        provider: "netlify".to_string(),
+        provider_account_id: None,
+        included_teams: Vec::new(),
+        quota: Quota::default(),
+    };
+    register_account(&state, cached)
+}
+
+#[tauri::command]
+pub async fn add_render_account(token: String, state: State<'_, AppState>) -> Result<Account, AppError> {
+    // Initialize if needed
+    initialize_state(&state).await?;
+
+    let client = render::create_client(&token)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    // API keys aren't tied to a user endpoint, so verify by fetching owners
+    let owners = client
+        .list_owners()
+        .await
+        .map_err(|e| AppError::from(e).context("Invalid token"))?;
+
+    let owner = owners.first();
+    let owner_name = owner.map(|o| o.name.clone());
+
+    // Generate a unique ID based on token hash
+    let token_hash = format!("{:x}", md5::compute(&token));
+    let short_hash = &token_hash[..8];
+    let account_id = format!("render_{}", short_hash);
+    let display_name = owner_name.clone().unwrap_or_else(|| "Render Workspace".to_string());
+    let email = owner
+        .and_then(|o| o.email.clone())
+        .unwrap_or_else(|| "workspace@render.com".to_string());
+
+    // Cache in memory
+    let cached = CachedAccount {
+        id: account_id,
+        username: display_name.clone(),
+        email,
+        name: Some(display_name),
+        scope_type: "workspace".to_string(),
+        team_name: owner_name,
+        team_slug: None,
+        team_id: None,
+        token,
+        provider: "render".to_string(),
+        provider_account_id: None,
+        included_teams: Vec::new(),
+        quota: Quota::default(),
+    };
+    register_account(&state, cached)
+}
+
+#[tauri::command]
+pub async fn add_fly_account(token: String, state: State<'_, AppState>) -> Result<Account, AppError> {
+    // Initialize if needed
+    initialize_state(&state).await?;
+
+    let client = fly::create_client(&token)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    // Personal tokens can read the user, org tokens can only see their apps
+    let (account_id, username, email, name, scope_type, org_name) = match client.get_user().await {
+        Ok(user) => (
+            user.id,
+            user.name.clone().unwrap_or_else(|| user.email.clone()),
+            user.email,
+            user.name,
+            "user".to_string(),
+            None,
+        ),
+        Err(_) => {
+            let apps = client
+                .list_apps(1)
+                .await
+                .map_err(|e| AppError::from(e).context("Invalid token"))?;
+
+            let org_name = apps
+                .first()
+                .and_then(|a| a.organization.as_ref())
+                .map(|o| o.name.clone().unwrap_or_else(|| o.slug.clone()));
+
+            // Generate a unique ID based on token hash for org tokens
+            let token_hash = format!("{:x}", md5::compute(&token));
+            let short_hash = &token_hash[..8];
+            let display_name = org_name.clone().unwrap_or_else(|| "Fly.io Organization".to_string());
+            (
+                format!("fly_org_{}", short_hash),
+                display_name.clone(),
+                "org@fly.io".to_string(),
+                Some(display_name),
+                "team".to_string(),
+                org_name,
+            )
+        }
+    };
 
-    Ok(Account {
+    // Cache in memory
+    let cached = CachedAccount {
         id: account_id,
         username,
         email,
         name,
-        scope_type: if is_workspace_token { "workspace".to_string() } else { "project".to_string() },
-        team_name: workspace_name,
+        scope_type,
+        team_name: org_name,
         team_slug: None,
-        provider: "railway".to_string(),
-    })
+        team_id: None,
+        token,
+        provider: "fly".to_string(),
+        provider_account_id: None,
+        included_teams: Vec::new(),
+        quota: Quota::default(),
+    };
+    register_account(&state, cached)
+}
+
+#[tauri::command]
+pub async fn add_cloudflare_account(token: String, cloudflare_account_id: String, state: State<'_, AppState>) -> Result<Account, AppError> {
+    // Initialize if needed
+    initialize_state(&state).await?;
+
+    let client = cloudflare::create_client(&token, &cloudflare_account_id)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let verification = client
+        .verify_token()
+        .await
+        .map_err(|e| AppError::from(e).context("Invalid token"))?;
+    if verification.status != "active" {
+        return Err(AppError::unauthorized(format!("Invalid token: token is {}", verification.status)));
+    }
+
+    // Verify the token can read Pages projects in this account
+    client
+        .list_projects()
+        .await
+        .map_err(|e| AppError::from(e).context("Token can't access Pages for this account"))?;
+
+    // Account details need an extra permission, so fall back to the ID
+    let account_name = client.get_account().await.ok().map(|a| a.name);
+    let display_name = account_name.clone().unwrap_or_else(|| "Cloudflare Pages".to_string());
+
+    let account_id = format!("cloudflare_{}", verification.id);
+
+    // Cache in memory
+    let cached = CachedAccount {
+        id: account_id,
+        username: display_name.clone(),
+        email: "pages@cloudflare.com".to_string(),
+        name: Some(display_name),
+        scope_type: "team".to_string(),
+        team_name: account_name,
+        team_slug: None,
+        team_id: None,
+        token,
+        provider: "cloudflare".to_string(),
+        provider_account_id: Some(cloudflare_account_id),
+        included_teams: Vec::new(),
+        quota: Quota::default(),
+    };
+    register_account(&state, cached)
 }
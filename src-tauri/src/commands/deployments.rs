@@ -1,161 +1,248 @@
-use tauri::{State, AppHandle};
+use std::time::Duration;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
-use crate::vercel::{self, Deployment, Project};
-use crate::railway;
-use crate::state::AppState;
-use crate::commands::auth::{get_stored_token, initialize_state};
+use tauri::{AppHandle, Emitter, Manager, State};
+use crate::vercel::{Deployment, Project};
+use crate::analytics::{self, ProjectBuildStats};
+use crate::error::AppError;
+use crate::history::StatusEntry;
+use crate::http::RateLimitState;
+use crate::provider::{self, DeploymentPage, UnifiedDeployment};
+use crate::state::{AppState, CachedAccount};
+use crate::commands::auth::{get_vercel_client, get_vercel_deployment_client, initialize_state};
+use crate::tracker;
 use crate::tray;
 
-/// Unified deployment type that works across providers
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct UnifiedDeployment {
-    pub id: String,
-    pub provider: String,  // "vercel" or "railway"
-    pub name: String,
-    pub url: Option<String>,
-    pub status: String,
-    pub created_at: Option<i64>,  // Unix timestamp in ms
-    pub commit_message: Option<String>,
-    pub branch: Option<String>,
-    pub project_id: Option<String>,
-    pub service_id: Option<String>,
-    pub git_author_login: Option<String>,  // GitHub username for avatar
-    pub team_slug: Option<String>,  // Team/user slug for Vercel dashboard URLs
-    pub account_id: Option<String>,  // Account ID for fetching logs
-}
-
 #[tauri::command]
-pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
-    let token = get_stored_token(state)
-        .await?
-        .ok_or("Not authenticated")?;
-
-    let client = vercel::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<Project>, AppError> {
+    let client = get_vercel_client(&state, None).await?;
 
     client
         .list_projects()
         .await
-        .map_err(|e| format!("Failed to list projects: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to list projects"))
 }
 
+/// Latest deployments, or only those created after `since` (unix ms)
 #[tauri::command]
-pub async fn list_deployments(project_id: Option<String>, limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<Deployment>, String> {
-    let token = get_stored_token(state)
-        .await?
-        .ok_or("Not authenticated")?;
+pub async fn list_deployments(
+    project_id: Option<String>,
+    limit: Option<u32>,
+    since: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Deployment>, AppError> {
+    let client = get_vercel_client(&state, None).await?;
+    let limit = limit.unwrap_or(20);
 
-    let client = vercel::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+    let deployments = match since {
+        Some(since) => client.list_deployments_since(project_id.as_deref(), since, limit).await,
+        None => client.list_deployments(project_id.as_deref(), limit).await,
+    };
+    deployments.map_err(|e| AppError::from(e).context("Failed to list deployments"))
+}
+
+#[tauri::command]
+pub async fn get_deployment(deployment_id: String, state: State<'_, AppState>) -> Result<Deployment, AppError> {
+    let client = get_vercel_deployment_client(&state, None, &deployment_id).await?;
 
     client
-        .list_deployments(project_id.as_deref(), limit.unwrap_or(20))
+        .get_deployment(&deployment_id)
         .await
-        .map_err(|e| format!("Failed to list deployments: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to get deployment"))
+}
+
+// Vercel deployment actions, each returning the resulting deployment
+#[derive(Debug, Clone, Copy)]
+enum DeploymentAction {
+    Cancel,
+    Redeploy,
+    RedeployWithoutCache,
+    Promote,
+    Rollback,
+}
+
+async fn run_deployment_action(
+    state: &AppState,
+    deployment_id: &str,
+    account_id: Option<String>,
+    action: DeploymentAction,
+) -> Result<UnifiedDeployment, AppError> {
+    let client = get_vercel_deployment_client(state, account_id.clone(), deployment_id).await?;
+
+    let deployment = match action {
+        DeploymentAction::Cancel => client.cancel_deployment(deployment_id).await,
+        DeploymentAction::Redeploy => client.redeploy(deployment_id, true).await,
+        DeploymentAction::RedeployWithoutCache => client.redeploy(deployment_id, false).await,
+        DeploymentAction::Promote => client.promote_deployment(deployment_id).await,
+        DeploymentAction::Rollback => client.rollback_deployment(deployment_id).await,
+    }?;
+
+    // Pick the new state up right away
+    state.scheduler.wake();
+
+    let mut unified = client.to_unified(deployment);
+    unified.account_id = account_id.or_else(|| state.get_active_account_id());
+    Ok(unified)
 }
 
 #[tauri::command]
-pub async fn get_deployment(deployment_id: String, state: State<'_, AppState>) -> Result<Deployment, String> {
-    let token = get_stored_token(state)
-        .await?
-        .ok_or("Not authenticated")?;
+pub async fn cancel_deployment(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<UnifiedDeployment, AppError> {
+    run_deployment_action(&state, &deployment_id, account_id, DeploymentAction::Cancel).await
+}
 
-    let client = vercel::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+#[tauri::command]
+pub async fn redeploy_deployment(
+    deployment_id: String,
+    use_build_cache: Option<bool>,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<UnifiedDeployment, AppError> {
+    let action = if use_build_cache.unwrap_or(true) {
+        DeploymentAction::Redeploy
+    } else {
+        DeploymentAction::RedeployWithoutCache
+    };
+    run_deployment_action(&state, &deployment_id, account_id, action).await
+}
 
-    client
-        .get_deployment(&deployment_id)
+#[tauri::command]
+pub async fn promote_deployment(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<UnifiedDeployment, AppError> {
+    run_deployment_action(&state, &deployment_id, account_id, DeploymentAction::Promote).await
+}
+
+#[tauri::command]
+pub async fn rollback_deployment(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<UnifiedDeployment, AppError> {
+    run_deployment_action(&state, &deployment_id, account_id, DeploymentAction::Rollback).await
+}
+
+// How long one account may take before it's reported as timed out
+const ACCOUNT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Outcome of fetching one account's deployments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AccountFetchStatus {
+    Ok,
+    AuthFailed { message: String },
+    RateLimited { message: String },
+    TimedOut,
+    Failed { message: String },
+}
+
+impl From<AppError> for AccountFetchStatus {
+    fn from(e: AppError) -> Self {
+        match e {
+            AppError::Unauthorized { message } | AppError::Forbidden { message } => {
+                AccountFetchStatus::AuthFailed { message }
+            }
+            AppError::RateLimited { message, .. } => AccountFetchStatus::RateLimited { message },
+            other => AccountFetchStatus::Failed { message: other.to_string() },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFetchResult {
+    pub account_id: String,
+    pub provider: String,
+    pub status: AccountFetchStatus,
+}
+
+// Latest deployments of one account, bounded by ACCOUNT_TIMEOUT
+async fn fetch_account_deployments(account: &CachedAccount, limit: u32) -> Result<Vec<UnifiedDeployment>, AccountFetchStatus> {
+    let provider = provider::create_provider(account)
+        .map_err(|e| AccountFetchStatus::from(AppError::from(e)))?;
+
+    let mut deployments = tokio::time::timeout(ACCOUNT_TIMEOUT, provider.list_deployments(limit))
         .await
-        .map_err(|e| format!("Failed to get deployment: {}", e))
+        .map_err(|_| AccountFetchStatus::TimedOut)?
+        .map_err(|e| AccountFetchStatus::from(AppError::from(e)))?;
+
+    for d in &mut deployments {
+        d.account_id = Some(account.id.clone());
+    }
+    Ok(deployments)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllDeploymentsResponse {
+    pub deployments: Vec<UnifiedDeployment>,
+    pub accounts: Vec<AccountFetchResult>,
+    // Served from local history; fresh results follow in a `deployments-refreshed` event
+    pub cached: bool,
 }
 
-/// List deployments from ALL accounts (both Vercel and Railway)
+/// List deployments from ALL accounts across every provider.
+///
+/// When history has deployments for these accounts they are returned right
+/// away and refreshed in the background. Otherwise accounts are fetched
+/// concurrently; one failing or slow account only shows up in its own status.
 #[tauri::command]
-pub async fn list_all_deployments(limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<UnifiedDeployment>, String> {
+pub async fn list_all_deployments(limit: Option<u32>, app: AppHandle, state: State<'_, AppState>) -> Result<AllDeploymentsResponse, AppError> {
     initialize_state(&state).await?;
 
     let limit = limit.unwrap_or(8);
-    let mut all_deployments: Vec<UnifiedDeployment> = Vec::new();
+    let account_ids: Vec<String> = state.get_all_accounts().into_iter().map(|a| a.id).collect();
+    let cached = state.history.latest(&account_ids, limit as usize);
+
+    if cached.is_empty() {
+        return Ok(fetch_all_deployments(&app, &state, limit).await);
+    }
 
-    // Get all accounts
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = handle.state::<AppState>();
+        let response = fetch_all_deployments(&handle, &state, limit).await;
+        let _ = handle.emit("deployments-refreshed", &response);
+    });
+
+    Ok(AllDeploymentsResponse {
+        deployments: cached,
+        accounts: state.get_fetch_results(&account_ids),
+        cached: true,
+    })
+}
+
+// Latest deployments of every account, recorded in history
+async fn fetch_all_deployments(app: &AppHandle, state: &AppState, limit: u32) -> AllDeploymentsResponse {
     let accounts = state.get_all_accounts();
 
-    for account in accounts {
-        match account.provider.as_str() {
-            "vercel" => {
-                let client = match vercel::create_client(&account.token) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-                // Get team slug for dashboard URLs (use team_slug if team, else username)
-                let team_slug = account.team_slug.clone().unwrap_or_else(|| account.username.clone());
-
-                // Fetch deployments at team level (not per project)
-                if let Ok(deployments) = client.list_deployments(None, limit).await {
-                    for d in deployments {
-                        let status = match d.state.as_ref().or(d.ready_state.as_ref()) {
-                            Some(s) => format!("{:?}", s).to_uppercase(),
-                            None => "UNKNOWN".to_string(),
-                        };
-
-                        all_deployments.push(UnifiedDeployment {
-                            id: d.uid.clone(),
-                            provider: "vercel".to_string(),
-                            name: d.name.clone(),
-                            url: Some(format!("https://{}", d.url)),
-                            status,
-                            created_at: d.created_at,
-                            commit_message: d.meta.as_ref().and_then(|m| m.commit_message.clone()),
-                            branch: d.meta.as_ref().and_then(|m| m.branch.clone()),
-                            project_id: Some(d.name.clone()),
-                            service_id: None,
-                            git_author_login: d.meta.as_ref().and_then(|m| m.git_author_login.clone()),
-                            team_slug: Some(team_slug.clone()),
-                            account_id: Some(account.id.clone()),
-                        });
-                    }
-                }
+    let results = join_all(
+        accounts
+            .iter()
+            .map(|account| fetch_account_deployments(account, limit)),
+    )
+    .await;
+
+    let mut all_deployments: Vec<UnifiedDeployment> = Vec::new();
+    let mut statuses = Vec::new();
+
+    for (account, result) in accounts.iter().zip(results) {
+        let status = match result {
+            Ok(deployments) => {
+                state.history.record(&deployments);
+                let changes = state.tracker.observe(&account.id, &deployments);
+                tracker::publish_changes(app, &changes);
+
+                all_deployments.extend(deployments);
+                AccountFetchStatus::Ok
             }
-            "railway" => {
-                // Use the correct client type based on the account scope
-                let token_type = if account.scope_type == "project" { "project" } else { "workspace" };
-                let client = match railway::create_client_with_type(&account.token, token_type) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-                if let Ok(deployments) = client.list_deployments(None, None, None, limit).await {
-                    for d in deployments {
-                        // Parse ISO timestamp to unix ms
-                        let created_at = d.created_at.as_ref().and_then(|s| {
-                            chrono::DateTime::parse_from_rfc3339(s)
-                                .ok()
-                                .map(|dt| dt.timestamp_millis())
-                        });
-
-                        all_deployments.push(UnifiedDeployment {
-                            id: d.id.clone(),
-                            provider: "railway".to_string(),
-                            name: d.service_name.clone().unwrap_or_else(|| "Deployment".to_string()),
-                            url: d.static_url.clone(),
-                            status: format!("{:?}", d.status).to_uppercase(),
-                            created_at,
-                            commit_message: d.meta.as_ref().and_then(|m| m.commit_message.clone()),
-                            branch: d.meta.as_ref().and_then(|m| m.branch.clone()),
-                            project_id: d.project_id.clone(),
-                            service_id: d.service_id.clone(),
-                            git_author_login: None,  // Railway doesn't expose git author
-                            team_slug: None,  // Not used for Railway
-                            account_id: Some(account.id.clone()),
-                        });
-                    }
-                }
+            Err(status) => {
+                eprintln!("[DEPLOYMENTS] {} failed: {:?}", account.id, status);
+                status
             }
-            _ => {}
-        }
+        };
+
+        statuses.push(AccountFetchResult {
+            account_id: account.id.clone(),
+            provider: account.provider.clone(),
+            status,
+        });
     }
+    tray::refresh(app);
+    state.set_fetch_results(&statuses);
 
     // Sort by created_at descending (most recent first)
     all_deployments.sort_by(|a, b| {
@@ -167,20 +254,122 @@ pub async fn list_all_deployments(limit: Option<u32>, state: State<'_, AppState>
     // Truncate to limit
     all_deployments.truncate(limit as usize);
 
-    Ok(all_deployments)
+    AllDeploymentsResponse {
+        deployments: all_deployments,
+        accounts: statuses,
+        cached: false,
+    }
 }
 
-/// Update tray icon based on build status
+/// Older deployments of one account, for scrolling back through history
 #[tauri::command]
-pub fn update_tray_status(is_building: bool, building_project: Option<String>, app: AppHandle) {
-    // Set global flag so background thread knows to start polling
-    crate::set_building_flag(is_building);
+pub async fn load_more_deployments(
+    account_id: String,
+    cursor: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<DeploymentPage, AppError> {
+    initialize_state(&state).await?;
 
-    if is_building {
-        tray::set_tray_building(&app, building_project.as_deref());
-    } else {
-        tray::set_tray_normal(&app);
+    let account = state.get_account(&account_id).ok_or_else(|| AppError::not_found("Account not found"))?;
+    let provider = provider::create_provider(&account)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let mut page = provider
+        .list_deployments_page(limit.unwrap_or(20), cursor)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to load deployments"))?;
+
+    for d in &mut page.deployments {
+        d.account_id = Some(account.id.clone());
     }
+    state.history.record(&page.deployments);
+
+    Ok(page)
+}
+
+/// Every status a deployment was seen in, oldest first
+#[tauri::command]
+pub fn get_status_history(deployment_id: String, state: State<'_, AppState>) -> Vec<StatusEntry> {
+    state.history.status_history(&deployment_id)
+}
+
+// Deployments fetched per account to top up history before computing build stats
+const STATS_BACKFILL_LIMIT: u32 = 50;
+
+/// Build time statistics per project over the last `window` successful
+/// builds, from local history topped up with each account's latest deployments
+#[tauri::command]
+pub async fn get_build_stats(window: Option<u32>, state: State<'_, AppState>) -> Result<Vec<ProjectBuildStats>, AppError> {
+    initialize_state(&state).await?;
+
+    let accounts = state.get_all_accounts();
+    let results = join_all(
+        accounts
+            .iter()
+            .map(|account| fetch_account_deployments(account, STATS_BACKFILL_LIMIT)),
+    )
+    .await;
+
+    for (account, result) in accounts.iter().zip(results) {
+        match result {
+            Ok(deployments) => state.history.record(&deployments),
+            // Stats still cover what history already has
+            Err(status) => eprintln!("[ANALYTICS] Couldn't refresh {}: {:?}", account.id, status),
+        }
+    }
+
+    let account_ids: Vec<String> = accounts.into_iter().map(|a| a.id).collect();
+    let deployments = state.history.latest(&account_ids, usize::MAX);
+
+    Ok(analytics::build_stats(
+        deployments,
+        |id| state.history.status_history(id),
+        window.unwrap_or(20) as usize,
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRateLimit {
+    pub account_id: String,
+    pub provider: String,
+    pub state: RateLimitState,
+}
+
+/// Last known rate limit of every account that reported one
+#[tauri::command]
+pub async fn get_rate_limits(state: State<'_, AppState>) -> Result<Vec<AccountRateLimit>, AppError> {
+    initialize_state(&state).await?;
+
+    Ok(state
+        .get_all_accounts()
+        .into_iter()
+        .filter_map(|account| {
+            account.quota.state().map(|limit| AccountRateLimit {
+                account_id: account.id,
+                provider: account.provider,
+                state: limit,
+            })
+        })
+        .collect())
+}
+
+/// Called when the UI's deployment list changes. The tray follows the
+/// scheduler's polls, so a new build only needs a poll to show up.
+#[tauri::command]
+pub fn update_tray_status(is_building: bool, building_project: Option<String>, state: State<'_, AppState>) {
+    if is_building && !state.tray_status.is_building() {
+        eprintln!("[TRAY] UI saw {} building, polling now", building_project.as_deref().unwrap_or("a deployment"));
+        // Poll right away so the scheduler switches to the faster interval
+        state.scheduler.wake();
+    }
+}
+
+/// Clear the tray's failed state
+#[tauri::command]
+pub fn acknowledge_tray_failures(app: AppHandle) {
+    tray::acknowledge_failures(&app);
 }
 
 /// Send a deployment notification
@@ -189,15 +378,21 @@ pub async fn send_deployment_notification(
     title: String,
     body: String,
     app: AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use tauri_plugin_notification::NotificationExt;
 
+    if let Some(state) = app.try_state::<AppState>() {
+        if state.rules.is_quiet(chrono::Local::now().time()) {
+            return Ok(());
+        }
+    }
+
     app.notification()
         .builder()
         .title(&title)
         .body(&body)
         .show()
-        .map_err(|e| format!("Failed to send notification: {}", e))?;
+        .map_err(|e| AppError::provider(format!("Failed to send notification: {}", e)))?;
 
     Ok(())
 }
@@ -0,0 +1,91 @@
+use tauri::State;
+use crate::error::AppError;
+use crate::integrations::{Integration, IntegrationKind};
+use crate::provider::UnifiedDeployment;
+use crate::state::AppState;
+use crate::tracker::{DeploymentPhase, DeploymentStatusChange};
+use crate::commands::auth::{initialize_state, save_state_to_keychain};
+
+#[tauri::command]
+pub async fn list_integrations(state: State<'_, AppState>) -> Result<Vec<Integration>, AppError> {
+    initialize_state(&state).await?;
+    Ok(state.integrations.list())
+}
+
+/// Add an integration, or update it when the ID is already known.
+/// An empty ID gets a new one.
+#[tauri::command]
+pub async fn save_integration(mut integration: Integration, state: State<'_, AppState>) -> Result<Integration, AppError> {
+    initialize_state(&state).await?;
+
+    let url = integration.webhook_url.trim().to_string();
+    let expected_host = match integration.kind {
+        IntegrationKind::Slack => url.starts_with("https://hooks.slack.com/"),
+        IntegrationKind::Discord => {
+            url.starts_with("https://discord.com/api/webhooks/") || url.starts_with("https://discordapp.com/api/webhooks/")
+        }
+    };
+    if !expected_host {
+        return Err(AppError::provider(format!("Not a {:?} webhook URL", integration.kind)));
+    }
+    integration.webhook_url = url;
+
+    if integration.id.is_empty() {
+        integration.id = format!("integration_{}", chrono::Utc::now().timestamp_millis());
+    }
+
+    state.integrations.upsert(integration.clone());
+    save_state_to_keychain(&state)?;
+    Ok(integration)
+}
+
+#[tauri::command]
+pub async fn remove_integration(integration_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    initialize_state(&state).await?;
+
+    state.integrations.remove(&integration_id);
+    save_state_to_keychain(&state)
+}
+
+/// Post a sample failure so the user can check the channel
+#[tauri::command]
+pub async fn test_integration(integration_id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    initialize_state(&state).await?;
+
+    let integration = state
+        .integrations
+        .get(&integration_id)
+        .ok_or_else(|| AppError::not_found("Integration not found"))?;
+
+    let change = DeploymentStatusChange {
+        deployment: UnifiedDeployment {
+            id: "test".to_string(),
+            provider: "vercel".to_string(),
+            name: "example-app".to_string(),
+            url: None,
+            status: "ERROR".to_string(),
+            created_at: Some(chrono::Utc::now().timestamp_millis()),
+            commit_message: Some("Test notification from Deployment".to_string()),
+            branch: Some("main".to_string()),
+            project_id: None,
+            service_id: None,
+            git_author_login: None,
+            team_slug: None,
+            account_id: None,
+            queue_ms: None,
+            build_ms: Some(83_000),
+            environment: Some("production".to_string()),
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links(),
+        previous: Some(DeploymentPhase::Building),
+        current: DeploymentPhase::Error,
+    };
+
+    state
+        .integrations
+        .send(&integration, &change)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to post test message"))
+}
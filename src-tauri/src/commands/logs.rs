@@ -1,35 +1,56 @@
 use futures::StreamExt;
 use tauri::{AppHandle, Emitter, State, Manager};
-use crate::commands::auth::get_stored_token;
-use crate::state::AppState;
+use crate::commands::auth::{get_vercel_deployment_client, initialize_state};
+use crate::error::AppError;
+use crate::provider;
+use crate::state::{AppState, CachedAccount};
 use crate::vercel::LogLine;
 
+// Account that owns a deployment, if it isn't a Vercel account
+fn provider_account(state: &AppState, account_id: Option<&str>) -> Option<CachedAccount> {
+    account_id
+        .and_then(|id| state.get_account(id))
+        .filter(|a| a.provider != "vercel")
+}
+
+// Follow logs through the account's provider, emitting the same events as Vercel streams
+async fn stream_provider_logs(app: &AppHandle, account: &CachedAccount, deployment_id: &str) -> Result<(), AppError> {
+    let provider = provider::create_provider(account)
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+    let result = provider
+        .stream_logs(deployment_id, &mut |line| {
+            let _ = app.emit("deployment-log", &line);
+        })
+        .await;
+
+    if let Err(e) = result {
+        let _ = app.emit("deployment-log-error", format!("Stream error: {}", e));
+    }
+
+    let _ = app.emit("deployment-log-complete", deployment_id);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn stream_deployment_logs(
     app: AppHandle,
     deployment_id: String,
+    account_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let token = get_stored_token(state)
-        .await?
-        .ok_or("Not authenticated")?;
+) -> Result<(), AppError> {
+    initialize_state(&state).await?;
+
+    if let Some(account) = provider_account(&state, account_id.as_deref()) {
+        return stream_provider_logs(&app, &account, &deployment_id).await;
+    }
 
-    let url = format!(
-        "https://api.vercel.com/v3/deployments/{}/events?follow=1&build=1",
-        deployment_id
-    );
+    let client = get_vercel_deployment_client(&state, account_id, &deployment_id).await?;
 
-    let client = reqwest::Client::new();
     let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
+        .get_deployment_events(&deployment_id, true)
         .await
-        .map_err(|e| format!("Failed to connect: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Failed to stream logs: {}", response.status()));
-    }
+        .map_err(|e| AppError::from(e).context("Failed to stream logs"))?;
 
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
@@ -86,98 +107,59 @@ pub async fn stream_deployment_logs(
 }
 
 #[tauri::command]
-pub async fn fetch_deployment_logs(deployment_id: String, state: State<'_, AppState>) -> Result<Vec<LogLine>, String> {
-    let token = get_stored_token(state)
-        .await?
-        .ok_or("Not authenticated")?;
-
-    let url = format!(
-        "https://api.vercel.com/v3/deployments/{}/events?build=1",
-        deployment_id
-    );
+pub async fn fetch_deployment_logs(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<Vec<LogLine>, AppError> {
+    initialize_state(&state).await?;
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch logs: {}", e))?;
+    if let Some(account) = provider_account(&state, account_id.as_deref()) {
+        let provider = provider::create_provider(&account)
+            .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
-    if !response.status().is_success() {
-        return Err(format!("Failed to fetch logs: {}", response.status()));
+        return provider
+            .fetch_logs(&deployment_id)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to fetch logs"));
     }
 
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    let mut logs = Vec::new();
-
-    for line in text.lines() {
-        if line.starts_with("data: ") {
-            let data = &line[6..];
-            if let Ok(event) = serde_json::from_str::<serde_json::Value>(data) {
-                if let Some(payload) = event.get("payload") {
-                    if let Some(text) = payload.get("text").and_then(|t| t.as_str()) {
-                        let is_error = text.to_lowercase().contains("error")
-                            || text.to_lowercase().contains("failed");
-
-                        logs.push(LogLine {
-                            timestamp: event
-                                .get("created")
-                                .and_then(|c| c.as_i64())
-                                .unwrap_or(0),
-                            text: text.to_string(),
-                            is_error,
-                        });
-                    }
-                }
-            }
-        }
-    }
+    let client = get_vercel_deployment_client(&state, account_id, &deployment_id).await?;
 
-    Ok(logs)
+    client
+        .get_deployment_logs(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to fetch logs"))
 }
 
 #[tauri::command]
-pub async fn fetch_error_logs_text(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<String, String> {
-    // Get token for specific account if provided, otherwise use active account
-    let token = match account_id {
-        Some(id) => {
-            crate::commands::auth::get_account_token(id, state.clone())
-                .await?
-                .ok_or("Account not found")?
-        }
-        None => {
-            get_stored_token(state)
-                .await?
-                .ok_or("Not authenticated")?
-        }
-    };
+pub async fn fetch_error_logs_text(deployment_id: String, account_id: Option<String>, state: State<'_, AppState>) -> Result<String, AppError> {
+    initialize_state(&state).await?;
+
+    if let Some(account) = provider_account(&state, account_id.as_deref()) {
+        let provider = provider::create_provider(&account)
+            .map_err(|e| AppError::from(e).context("Failed to create client"))?;
+
+        let logs = provider
+            .fetch_logs(&deployment_id)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to fetch logs"))?;
+
+        let error_lines: Vec<&str> = logs.iter().filter(|l| l.is_error).map(|l| l.text.as_str()).collect();
+        return if error_lines.is_empty() {
+            Ok(logs.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n"))
+        } else {
+            Ok(error_lines.join("\n"))
+        };
+    }
 
-    let url = format!(
-        "https://api.vercel.com/v3/deployments/{}/events?build=1",
-        deployment_id
-    );
+    let client = get_vercel_deployment_client(&state, account_id, &deployment_id).await?;
 
-    let client = reqwest::Client::new();
     let response = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
+        .get_deployment_events(&deployment_id, false)
         .await
-        .map_err(|e| format!("Failed to fetch logs: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Failed to fetch logs: {}", response.status()));
-    }
+        .map_err(|e| AppError::from(e).context("Failed to fetch logs"))?;
 
     let text = response
         .text()
         .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to read response"))?;
 
     let mut all_lines: Vec<String> = Vec::new();
     let mut error_lines: Vec<String> = Vec::new();
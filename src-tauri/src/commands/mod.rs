@@ -1,9 +1,15 @@
 pub mod auth;
 pub mod deployments;
+pub mod integrations;
 pub mod logs;
+pub mod notifications;
 pub mod railway;
+pub mod webhooks;
 
 pub use auth::*;
 pub use deployments::*;
+pub use integrations::*;
 pub use logs::*;
+pub use notifications::*;
 pub use railway::*;
+pub use webhooks::*;
@@ -0,0 +1,51 @@
+use chrono::NaiveTime;
+use tauri::State;
+use crate::error::AppError;
+use crate::rules::{NotificationSettings, RuleAction};
+use crate::state::AppState;
+use crate::commands::auth::{initialize_state, save_state_to_keychain};
+
+#[tauri::command]
+pub async fn get_notification_settings(state: State<'_, AppState>) -> Result<NotificationSettings, AppError> {
+    initialize_state(&state).await?;
+    Ok(state.rules.get())
+}
+
+/// Replace the notification rules and quiet hours. Rules are checked in the
+/// order given; rules with an empty ID get a new one.
+#[tauri::command]
+pub async fn set_notification_settings(
+    mut settings: NotificationSettings,
+    state: State<'_, AppState>,
+) -> Result<NotificationSettings, AppError> {
+    initialize_state(&state).await?;
+
+    for time in [&settings.quiet_hours.start, &settings.quiet_hours.end] {
+        if NaiveTime::parse_from_str(time.trim(), "%H:%M").is_err() {
+            return Err(AppError::provider(format!("Invalid quiet hours time \"{}\", expected HH:MM", time)));
+        }
+    }
+
+    let integrations = state.integrations.list();
+    let now = chrono::Utc::now().timestamp_millis();
+
+    for (i, rule) in settings.rules.iter_mut().enumerate() {
+        if rule.id.is_empty() {
+            rule.id = format!("rule_{}_{}", now, i);
+        }
+        if rule.actions.is_empty() {
+            return Err(AppError::provider(format!("Rule \"{}\" has no actions", rule.name)));
+        }
+        for action in &rule.actions {
+            if let RuleAction::Webhook { integration_ids } = action {
+                if let Some(missing) = integration_ids.iter().find(|id| !integrations.iter().any(|i| &i.id == *id)) {
+                    return Err(AppError::not_found(format!("Integration {} not found", missing)));
+                }
+            }
+        }
+    }
+
+    state.rules.set(settings);
+    save_state_to_keychain(&state)?;
+    Ok(state.rules.get())
+}
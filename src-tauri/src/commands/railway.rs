@@ -1,34 +1,35 @@
 use tauri::State;
 use crate::railway::{self, RailwayDeployment, RailwayProject, RailwayUser};
+use crate::error::AppError;
 use crate::state::AppState;
-use crate::commands::auth::{get_stored_token, initialize_state};
+use crate::commands::auth::{get_railway_client, get_stored_token, initialize_state};
 
 #[tauri::command]
-pub async fn railway_verify_token(token: String) -> Result<RailwayUser, String> {
+pub async fn railway_verify_token(token: String) -> Result<RailwayUser, AppError> {
     let client = railway::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     client
         .get_user()
         .await
-        .map_err(|e| format!("Invalid token: {}", e))
+        .map_err(|e| AppError::from(e).context("Invalid token"))
 }
 
 #[tauri::command]
-pub async fn railway_list_projects(state: State<'_, AppState>) -> Result<Vec<RailwayProject>, String> {
+pub async fn railway_list_projects(state: State<'_, AppState>) -> Result<Vec<RailwayProject>, AppError> {
     initialize_state(&state).await?;
 
     let token = get_stored_token(state)
         .await?
-        .ok_or("Not authenticated")?;
+        .ok_or_else(|| AppError::unauthorized("Not authenticated"))?;
 
     let client = railway::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     client
-        .list_projects(Some(50))
+        .list_projects(None)
         .await
-        .map_err(|e| format!("Failed to list projects: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to list projects"))
 }
 
 #[tauri::command]
@@ -38,15 +39,15 @@ pub async fn railway_list_deployments(
     environment_id: Option<String>,
     limit: Option<u32>,
     state: State<'_, AppState>,
-) -> Result<Vec<RailwayDeployment>, String> {
+) -> Result<Vec<RailwayDeployment>, AppError> {
     initialize_state(&state).await?;
 
     let token = get_stored_token(state)
         .await?
-        .ok_or("Not authenticated")?;
+        .ok_or_else(|| AppError::unauthorized("Not authenticated"))?;
 
     let client = railway::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     client
         .list_deployments(
@@ -56,25 +57,110 @@ pub async fn railway_list_deployments(
             limit.unwrap_or(20),
         )
         .await
-        .map_err(|e| format!("Failed to list deployments: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to list deployments"))
 }
 
 #[tauri::command]
 pub async fn railway_get_deployment(
     deployment_id: String,
     state: State<'_, AppState>,
-) -> Result<RailwayDeployment, String> {
+) -> Result<RailwayDeployment, AppError> {
     initialize_state(&state).await?;
 
     let token = get_stored_token(state)
         .await?
-        .ok_or("Not authenticated")?;
+        .ok_or_else(|| AppError::unauthorized("Not authenticated"))?;
 
     let client = railway::create_client(&token)
-        .map_err(|e| format!("Failed to create client: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to create client"))?;
 
     client
         .get_deployment(&deployment_id)
         .await
-        .map_err(|e| format!("Failed to get deployment: {}", e))
+        .map_err(|e| AppError::from(e).context("Failed to get deployment"))
+}
+
+#[tauri::command]
+pub async fn railway_redeploy_deployment(
+    deployment_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RailwayDeployment, AppError> {
+    let client = get_railway_client(&state, account_id).await?;
+
+    let deployment = client
+        .redeploy_deployment(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to redeploy"))?;
+
+    state.scheduler.wake();
+    Ok(deployment)
+}
+
+#[tauri::command]
+pub async fn railway_restart_deployment(
+    deployment_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RailwayDeployment, AppError> {
+    let client = get_railway_client(&state, account_id).await?;
+
+    let deployment = client
+        .restart_deployment(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to restart deployment"))?;
+
+    state.scheduler.wake();
+    Ok(deployment)
+}
+
+#[tauri::command]
+pub async fn railway_rollback_deployment(
+    deployment_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RailwayDeployment, AppError> {
+    let client = get_railway_client(&state, account_id).await?;
+
+    let deployment = client
+        .rollback_deployment(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to roll back"))?;
+
+    state.scheduler.wake();
+    Ok(deployment)
+}
+
+#[tauri::command]
+pub async fn railway_cancel_deployment(
+    deployment_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RailwayDeployment, AppError> {
+    let client = get_railway_client(&state, account_id).await?;
+
+    let deployment = client
+        .cancel_deployment(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to cancel deployment"))?;
+
+    state.scheduler.wake();
+    Ok(deployment)
+}
+
+#[tauri::command]
+pub async fn railway_remove_deployment(
+    deployment_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let client = get_railway_client(&state, account_id).await?;
+
+    client
+        .remove_deployment(&deployment_id)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to remove deployment"))?;
+
+    state.scheduler.wake();
+    Ok(())
 }
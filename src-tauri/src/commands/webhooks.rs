@@ -0,0 +1,38 @@
+use tauri::{AppHandle, State};
+use crate::error::AppError;
+use crate::state::AppState;
+use crate::webhook::WebhookConfig;
+use crate::commands::auth::{initialize_state, save_state_to_keychain};
+
+#[tauri::command]
+pub async fn get_webhook_config(state: State<'_, AppState>) -> Result<WebhookConfig, AppError> {
+    initialize_state(&state).await?;
+    Ok(state.webhook.config())
+}
+
+/// Save the webhook listener settings and restart it with them.
+///
+/// If the new settings can't be used (e.g. the port is taken), the previous
+/// ones stay in place.
+#[tauri::command]
+pub async fn set_webhook_config(config: WebhookConfig, app: AppHandle, state: State<'_, AppState>) -> Result<WebhookConfig, AppError> {
+    initialize_state(&state).await?;
+
+    if config.enabled && config.vercel_secret.is_none() && config.railway_secret.is_none() {
+        return Err(AppError::provider("Set a Vercel or Railway webhook secret first"));
+    }
+
+    let previous = state.webhook.config();
+    state.webhook.set_config(config);
+
+    if let Err(e) = state.webhook.restart(app.clone()).await {
+        state.webhook.set_config(previous);
+        if let Err(e) = state.webhook.restart(app).await {
+            eprintln!("[WEBHOOK] Failed to restore previous listener: {}", e);
+        }
+        return Err(AppError::from(e));
+    }
+
+    save_state_to_keychain(&state)?;
+    Ok(state.webhook.config())
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Error returned by every command, tagged by `kind` so the frontend can
+/// tell an expired token from a network blip without matching strings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AppError {
+    Unauthorized { message: String },
+    Forbidden { message: String },
+    #[serde(rename_all = "camelCase")]
+    RateLimited {
+        // Seconds until the provider accepts requests again, if known
+        retry_after: Option<u64>,
+        message: String,
+    },
+    Network { message: String },
+    NotFound { message: String },
+    Provider { code: Option<String>, message: String },
+    Keychain { message: String },
+}
+
+impl AppError {
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        AppError::Unauthorized { message: message.into() }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        AppError::NotFound { message: message.into() }
+    }
+
+    pub fn provider(message: impl Into<String>) -> Self {
+        AppError::Provider { code: None, message: message.into() }
+    }
+
+    pub fn keychain(message: impl Into<String>) -> Self {
+        AppError::Keychain { message: message.into() }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Unauthorized { message }
+            | AppError::Forbidden { message }
+            | AppError::RateLimited { message, .. }
+            | AppError::Network { message }
+            | AppError::NotFound { message }
+            | AppError::Provider { message, .. }
+            | AppError::Keychain { message } => message,
+        }
+    }
+
+    fn message_mut(&mut self) -> &mut String {
+        match self {
+            AppError::Unauthorized { message }
+            | AppError::Forbidden { message }
+            | AppError::RateLimited { message, .. }
+            | AppError::Network { message }
+            | AppError::NotFound { message }
+            | AppError::Provider { message, .. }
+            | AppError::Keychain { message } => message,
+        }
+    }
+
+    /// Prefix the message, e.g. with the operation that failed
+    pub fn context(mut self, context: &str) -> Self {
+        let message = self.message_mut();
+        *message = format!("{}: {}", context, message);
+        self
+    }
+
+    /// Classify an HTTP status
+    pub fn from_status(status: StatusCode, retry_after: Option<u64>, code: Option<String>, message: String) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => AppError::Unauthorized { message },
+            StatusCode::FORBIDDEN => AppError::Forbidden { message },
+            StatusCode::NOT_FOUND => AppError::NotFound { message },
+            StatusCode::TOO_MANY_REQUESTS => AppError::RateLimited { retry_after, message },
+            _ => AppError::Provider {
+                code: code.or_else(|| Some(status.as_u16().to_string())),
+                message,
+            },
+        }
+    }
+
+    /// Error for a non-success response, with the provider's own error
+    /// code and message when the body has them
+    pub async fn from_response(context: &str, resp: reqwest::Response) -> Self {
+        let status = resp.status();
+        let retry_after = retry_after(resp.headers());
+        let body = resp.text().await.unwrap_or_default();
+        let (code, detail) = parse_error_body(&body);
+
+        let message = match detail {
+            Some(detail) => format!("{}: {} - {}", context, status, detail),
+            None => format!("{}: {}", context, status),
+        };
+
+        AppError::from_status(status, retry_after, code, message)
+    }
+
+    /// Classify a GraphQL error, which arrives with a 200 status
+    pub fn from_graphql(code: Option<String>, message: String) -> Self {
+        let lower = message.to_lowercase();
+        let code_lower = code.as_deref().unwrap_or("").to_lowercase();
+
+        if lower.contains("not authorized") || lower.contains("unauthorized") || lower.contains("unauthenticated")
+            || code_lower == "unauthenticated"
+        {
+            AppError::Unauthorized { message }
+        } else if lower.contains("forbidden") || lower.contains("permission") || code_lower == "forbidden" {
+            AppError::Forbidden { message }
+        } else if lower.contains("rate limit") || lower.contains("too many requests") || code_lower == "ratelimited" {
+            AppError::RateLimited { retry_after: None, message }
+        } else if lower.contains("not found") || code_lower == "not_found" {
+            AppError::NotFound { message }
+        } else {
+            AppError::Provider { code, message }
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        from_reqwest(&e, e.to_string())
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    fn from(e: anyhow::Error) -> Self {
+        // Clients return AppErrors wrapped in anyhow, possibly with context on top
+        for cause in e.chain() {
+            if let Some(app_error) = cause.downcast_ref::<AppError>() {
+                return app_error.clone();
+            }
+            if let Some(reqwest_error) = cause.downcast_ref::<reqwest::Error>() {
+                return from_reqwest(reqwest_error, format!("{:#}", e));
+            }
+        }
+        AppError::provider(format!("{:#}", e))
+    }
+}
+
+fn from_reqwest(e: &reqwest::Error, message: String) -> AppError {
+    match e.status() {
+        Some(status) => AppError::from_status(status, None, None, message),
+        // Unexpected response shape
+        None if e.is_decode() => AppError::provider(message),
+        None => AppError::Network { message },
+    }
+}
+
+// Retry-After in seconds, or the reset time of X-RateLimit-Reset (unix seconds)
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(seconds) = header("retry-after").and_then(|v| v.parse::<u64>().ok()) {
+        return Some(seconds);
+    }
+
+    let reset = header("x-ratelimit-reset").and_then(|v| v.parse::<i64>().ok())?;
+    let now = chrono::Utc::now().timestamp();
+    Some((reset - now).max(0) as u64)
+}
+
+// Error code and message from common JSON error bodies: `{"error": {"code", "message"}}`
+// (Vercel), `{"errors": [{"code", "message"}]}` (Cloudflare) or `{"code", "message"}`
+fn parse_error_body(body: &str) -> (Option<String>, Option<String>) {
+    let value: serde_json::Value = match serde_json::from_str(body) {
+        Ok(value) => value,
+        Err(_) => return (None, None),
+    };
+    let error = value
+        .get("error")
+        .filter(|e| e.is_object())
+        .or_else(|| value.get("errors").and_then(|e| e.get(0)))
+        .unwrap_or(&value);
+
+    let code = error.get("code").and_then(|c| match c {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    });
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .map(|m| m.to_string());
+
+    (code, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(code: Option<&str>, message: &str) -> AppError {
+        AppError::from_graphql(code.map(str::to_string), message.to_string())
+    }
+
+    #[test]
+    fn from_graphql_classifies_by_message() {
+        assert!(matches!(classify(None, "Not Authorized"), AppError::Unauthorized { .. }));
+        assert!(matches!(classify(None, "User is unauthenticated"), AppError::Unauthorized { .. }));
+        assert!(matches!(classify(None, "Missing permission to read project"), AppError::Forbidden { .. }));
+        assert!(matches!(classify(None, "Forbidden"), AppError::Forbidden { .. }));
+        assert!(matches!(classify(None, "Rate limit exceeded"), AppError::RateLimited { retry_after: None, .. }));
+        assert!(matches!(classify(None, "Too Many Requests"), AppError::RateLimited { .. }));
+        assert!(matches!(classify(None, "Project not found"), AppError::NotFound { .. }));
+    }
+
+    #[test]
+    fn from_graphql_classifies_by_code() {
+        assert!(matches!(classify(Some("UNAUTHENTICATED"), "Nope"), AppError::Unauthorized { .. }));
+        assert!(matches!(classify(Some("FORBIDDEN"), "Nope"), AppError::Forbidden { .. }));
+        assert!(matches!(classify(Some("RATELIMITED"), "Slow down"), AppError::RateLimited { .. }));
+        assert!(matches!(classify(Some("NOT_FOUND"), "Gone"), AppError::NotFound { .. }));
+    }
+
+    #[test]
+    fn from_graphql_checks_auth_before_other_matches() {
+        // "not authorized" wins over the "not found" it could also read as
+        assert!(matches!(
+            classify(None, "Not Authorized: deployment not found"),
+            AppError::Unauthorized { .. }
+        ));
+    }
+
+    #[test]
+    fn from_graphql_keeps_unknown_errors_as_provider_errors() {
+        match classify(Some("INTERNAL"), "Something broke") {
+            AppError::Provider { code, message } => {
+                assert_eq!(code.as_deref(), Some("INTERNAL"));
+                assert_eq!(message, "Something broke");
+            }
+            other => panic!("expected a provider error, got {:?}", other),
+        }
+        assert!(matches!(classify(None, "Problem processing request"), AppError::Provider { code: None, .. }));
+    }
+}
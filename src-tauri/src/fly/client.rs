@@ -0,0 +1,293 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
+use super::types::*;
+
+const GRAPHQL_API: &str = "https://api.fly.io/graphql";
+const MACHINES_API: &str = "https://api.machines.dev/v1";
+
+pub struct FlyClient {
+    client: reqwest::Client,
+    token: String,
+    quota: Quota,
+}
+
+impl FlyClient {
+    pub fn new(token: String) -> Result<Self> {
+        // Macaroon tokens from `fly tokens create` already carry their
+        // "FlyV1" scheme, personal access tokens use Bearer auth
+        let auth = if token.starts_with("FlyV1 ") {
+            token.clone()
+        } else {
+            format!("Bearer {}", token)
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&auth)?);
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self { client, token, quota: Quota::default() })
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    async fn execute_query<T: serde::de::DeserializeOwned>(&self, query: &str, variables: Option<serde_json::Value>) -> Result<T> {
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables.unwrap_or(serde_json::json!({}))
+        });
+
+        let resp = self
+            .client
+            .post(GRAPHQL_API)
+            .json(&body)
+            .send_query(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("GraphQL request failed", resp).await.into());
+        }
+
+        let response: GraphQLResponse<T> = resp.json().await?;
+
+        if let Some(errors) = response.errors {
+            if !errors.is_empty() {
+                let code = errors.iter().find_map(|e| e.extensions.as_ref().and_then(|x| x.code.clone()));
+                let message = format!("GraphQL errors: {}", errors.iter().map(|e| e.message.clone()).collect::<Vec<_>>().join(", "));
+                let error = AppError::from_graphql(code, message);
+                if let AppError::RateLimited { retry_after, .. } = &error {
+                    self.quota.mark_limited(*retry_after);
+                }
+                return Err(error.into());
+            }
+        }
+
+        response.data.ok_or_else(|| anyhow!("No data in GraphQL response"))
+    }
+
+    pub async fn get_user(&self) -> Result<FlyUser> {
+        let query = r#"
+            query {
+                viewer {
+                    id
+                    email
+                    name
+                }
+            }
+        "#;
+
+        let data: ViewerData = self.execute_query(query, None).await?;
+        Ok(data.viewer)
+    }
+
+    pub async fn list_apps(&self, limit: u32) -> Result<Vec<FlyApp>> {
+        let query = r#"
+            query Apps($first: Int!) {
+                apps(first: $first) {
+                    nodes {
+                        id
+                        name
+                        status
+                        hostname
+                        organization {
+                            slug
+                            name
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "first": limit
+        });
+
+        let data: AppsData = self.execute_query(query, Some(variables)).await?;
+        Ok(data.apps.nodes)
+    }
+
+    /// List an app's releases, newest first
+    pub async fn list_releases(&self, app_name: &str, limit: u32) -> Result<Vec<FlyRelease>> {
+        let query = format!(
+            r#"
+            query AppReleases($name: String!, $first: Int!) {{
+                app(name: $name) {{
+                    {}
+                }}
+            }}
+        "#,
+            APP_RELEASES_FIELDS
+        );
+
+        let variables = serde_json::json!({
+            "name": app_name,
+            "first": limit
+        });
+
+        let data: AppData = self.execute_query(&query, Some(variables)).await?;
+        Ok(data.app.into_releases())
+    }
+
+    /// Apps together with their latest releases, in a single request
+    pub async fn list_apps_with_releases(&self, apps: u32, limit: u32) -> Result<Vec<FlyApp>> {
+        let query = format!(
+            r#"
+            query AppsReleases($apps: Int!, $first: Int!) {{
+                apps(first: $apps) {{
+                    nodes {{
+                        {}
+                    }}
+                }}
+            }}
+        "#,
+            APP_RELEASES_FIELDS
+        );
+
+        let variables = serde_json::json!({
+            "apps": apps,
+            "first": limit
+        });
+
+        let data: AppsData = self.execute_query(&query, Some(variables)).await?;
+        Ok(data.apps.nodes)
+    }
+
+    pub async fn list_machines(&self, app_name: &str) -> Result<Vec<FlyMachine>> {
+        let resp = self
+            .client
+            .get(format!("{}/apps/{}/machines", MACHINES_API, app_name))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list machines", resp).await.into());
+        }
+
+        let machines: Vec<FlyMachine> = resp.json().await?;
+        Ok(machines)
+    }
+
+    /// Releases of an app, with the latest one flagged while its machines
+    /// are still rolling out
+    pub async fn list_app_deployments(&self, app_name: &str, limit: u32) -> Result<Vec<FlyRelease>> {
+        let mut releases = self.list_releases(app_name, limit).await?;
+        self.flag_rollout(app_name, &mut releases).await;
+        Ok(releases)
+    }
+
+    /// Latest releases across the first `MAX_APPS` apps
+    pub async fn list_all_deployments(&self, limit: u32) -> Result<Vec<FlyRelease>> {
+        let apps = self.list_apps_with_releases(MAX_APPS, limit).await?;
+
+        // Only apps with a fresh release need their machines checked
+        let per_app = futures::future::join_all(apps.into_iter().map(|app| async move {
+            let name = app.name.clone();
+            let mut releases = app.into_releases();
+            self.flag_rollout(&name, &mut releases).await;
+            releases
+        }))
+        .await;
+
+        let mut all_releases: Vec<FlyRelease> = per_app.into_iter().flatten().collect();
+
+        // Sort by created_at descending (most recent first)
+        all_releases.sort_by(|a, b| {
+            let a_time = a.created_at.as_deref().unwrap_or("");
+            let b_time = b.created_at.as_deref().unwrap_or("");
+            b_time.cmp(a_time)
+        });
+
+        all_releases.truncate(limit as usize);
+
+        Ok(all_releases)
+    }
+
+    /// Mark the latest release as rolling out while machines in the app's
+    /// process groups still run an older release
+    async fn flag_rollout(&self, app_name: &str, releases: &mut [FlyRelease]) {
+        let Some(latest) = releases.first_mut() else {
+            return;
+        };
+
+        if latest.in_progress {
+            latest.rolling_out = true;
+            return;
+        }
+        if !may_be_rolling_out(latest, chrono::Utc::now().timestamp_millis()) {
+            return;
+        }
+
+        // Apps without machines (or without access to them) just show releases
+        let machines = self.list_machines(app_name).await.unwrap_or_default();
+        latest.rolling_out = is_rolling_out(latest, &machines);
+    }
+}
+
+/// Most apps listed per poll; each may also cost a machines request
+const MAX_APPS: u32 = 20;
+
+/// How long after a release its machines are still checked for a rollout
+const ROLLOUT_WINDOW_MS: i64 = 30 * 60 * 1000;
+
+const APP_RELEASES_FIELDS: &str = r#"
+    id
+    name
+    hostname
+    releases: releasesUnprocessed(first: $first) {
+        nodes {
+            id
+            version
+            status
+            inProgress
+            description
+            reason
+            imageRef
+            createdAt
+            user {
+                email
+            }
+        }
+    }
+"#;
+
+// Rollouts follow a release closely, so older or failed releases skip the
+// machines request entirely
+fn may_be_rolling_out(release: &FlyRelease, now_ms: i64) -> bool {
+    if matches!(release.status, FlyReleaseStatus::Failed | FlyReleaseStatus::Interrupted) {
+        return false;
+    }
+    crate::provider::parse_timestamp(release.created_at.as_deref())
+        .is_some_and(|created| now_ms - created < ROLLOUT_WINDOW_MS)
+}
+
+// A release is still rolling out while any machine in one of the app's
+// process groups runs an older release. One-off machines (`fly machine
+// run`) have no process group and standbys never get updated in place, so
+// both are ignored, as is the machine state (autostart is not a rollout)
+fn is_rolling_out(release: &FlyRelease, machines: &[FlyMachine]) -> bool {
+    machines
+        .iter()
+        .filter(|m| m.state != "destroyed" && m.state != "destroying")
+        .filter(|m| m.process_group().is_some() && !m.is_standby())
+        .any(|m| m.release_version().is_some_and(|v| v < release.version))
+}
+
+pub fn create_client(token: &str) -> Result<FlyClient> {
+    FlyClient::new(token.to_string())
+}
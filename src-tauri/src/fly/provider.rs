@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crate::provider::{self, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use crate::vercel::LogLine;
+use super::client::{create_client, FlyClient};
+use super::types::*;
+
+impl From<FlyRelease> for UnifiedDeployment {
+    fn from(r: FlyRelease) -> Self {
+        // Machine rollouts show up as in-progress deployments
+        let status = if r.rolling_out && r.status != FlyReleaseStatus::Failed {
+            "DEPLOYING"
+        } else {
+            r.status.to_unified()
+        };
+
+        UnifiedDeployment {
+            id: deployment_id(&r),
+            provider: "fly".to_string(),
+            name: r.app_name.clone().unwrap_or_else(|| format!("v{}", r.version)),
+            url: r.hostname.as_ref().map(|h| format!("https://{}", h)),
+            status: status.to_string(),
+            created_at: provider::parse_timestamp(r.created_at.as_deref()),
+            commit_message: r.description.clone().or(r.reason.clone()),
+            branch: None,  // Fly releases aren't tied to git
+            project_id: r.app_name.clone(),
+            service_id: None,
+            git_author_login: None,
+            team_slug: None,  // Not used for Fly
+            account_id: None,
+            queue_ms: None,  // Releases have no build timestamps
+            build_ms: None,
+            environment: None,
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+// Deployment ids carry the app name ("app/release") so a single release can
+// be looked up without listing every app
+fn deployment_id(r: &FlyRelease) -> String {
+    match &r.app_name {
+        Some(app) => format!("{}/{}", app, r.id),
+        None => r.id.clone(),
+    }
+}
+
+#[async_trait]
+impl DeploymentProvider for FlyClient {
+    async fn validate_token(&self) -> Result<()> {
+        // Org and deploy tokens can't read `viewer`, so list an app instead
+        self.list_apps(1).await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let apps = self.list_apps(100).await?;
+        Ok(apps
+            .into_iter()
+            .map(|a| UnifiedProject {
+                id: a.id,
+                provider: "fly".to_string(),
+                name: a.name,
+                updated_at: None,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let releases = FlyClient::list_all_deployments(self, limit).await?;
+        Ok(releases.into_iter().map(UnifiedDeployment::from).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        // Releases can only be looked up through their app
+        let (app_name, release_id) = deployment_id
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Release {} not found", deployment_id))?;
+        let releases = self.list_app_deployments(app_name, 50).await?;
+        releases
+            .into_iter()
+            .find(|r| r.id == release_id)
+            .map(UnifiedDeployment::from)
+            .ok_or_else(|| anyhow!("Release {} not found", deployment_id))
+    }
+
+    async fn fetch_logs(&self, _deployment_id: &str) -> Result<Vec<LogLine>> {
+        Err(anyhow!("Logs are not supported for Fly.io releases yet"))
+    }
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    Ok(Box::new(create_client(&account.token)?.with_quota(account.quota.clone())))
+}
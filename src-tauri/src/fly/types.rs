@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// GraphQL response wrapper types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLResponse<T> {
+    pub data: Option<T>,
+    pub errors: Option<Vec<GraphQLError>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+    #[serde(default)]
+    pub extensions: Option<GraphQLErrorExtensions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLErrorExtensions {
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+// User types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewerData {
+    pub viewer: FlyUser,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlyUser {
+    pub id: String,
+    pub email: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+// App types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppsData {
+    pub apps: AppConnection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppConnection {
+    pub nodes: Vec<FlyApp>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppData {
+    pub app: FlyApp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlyApp {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    #[serde(default)]
+    pub organization: Option<FlyOrganization>,
+    #[serde(default)]
+    pub releases: Option<ReleaseConnection>,
+}
+
+impl FlyApp {
+    /// The app's releases, tagged with the app they belong to
+    pub fn into_releases(self) -> Vec<FlyRelease> {
+        let releases = self.releases.map(|r| r.nodes).unwrap_or_default();
+        releases
+            .into_iter()
+            .map(|mut r| {
+                r.app_name = Some(self.name.clone());
+                r.hostname = self.hostname.clone();
+                r
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlyOrganization {
+    pub slug: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+// Release types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseConnection {
+    pub nodes: Vec<FlyRelease>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlyReleaseStatus {
+    Pending,
+    Running,
+    Complete,
+    Succeeded,
+    Failed,
+    Interrupted,
+    #[serde(other)]
+    Unknown,
+}
+
+impl FlyReleaseStatus {
+    /// Convert Fly status to unified status string for frontend
+    pub fn to_unified(&self) -> &'static str {
+        match self {
+            FlyReleaseStatus::Pending => "QUEUED",
+            FlyReleaseStatus::Running => "BUILDING",
+            FlyReleaseStatus::Complete => "READY",
+            FlyReleaseStatus::Succeeded => "READY",
+            FlyReleaseStatus::Failed => "ERROR",
+            FlyReleaseStatus::Interrupted => "CANCELED",
+            FlyReleaseStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlyRelease {
+    pub id: String,
+    pub version: i64,
+    pub status: FlyReleaseStatus,
+    #[serde(default)]
+    pub in_progress: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub image_ref: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub user: Option<FlyReleaseUser>,
+    // Additional context for frontend
+    #[serde(skip_deserializing)]
+    pub app_name: Option<String>,
+    #[serde(skip_deserializing)]
+    pub hostname: Option<String>,
+    /// Set when the app's machines are still being rolled to this release
+    #[serde(skip_deserializing)]
+    pub rolling_out: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlyReleaseUser {
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+// Machines API types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlyMachine {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub image_ref: Option<FlyImageRef>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub config: Option<FlyMachineConfig>,
+}
+
+impl FlyMachine {
+    fn metadata(&self, key: &str) -> Option<&str> {
+        self.config.as_ref()?.metadata.get(key).map(String::as_str)
+    }
+
+    /// Process group from fly.toml; one-off machines don't have one
+    pub fn process_group(&self) -> Option<&str> {
+        self.metadata("fly_process_group")
+    }
+
+    /// Version of the release the machine was last deployed with
+    pub fn release_version(&self) -> Option<i64> {
+        self.metadata("fly_release_version")?.parse().ok()
+    }
+
+    /// Standby machines only take over when their primary fails
+    pub fn is_standby(&self) -> bool {
+        self.config.as_ref().is_some_and(|c| !c.standbys.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FlyMachineConfig {
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub standbys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlyImageRef {
+    #[serde(default)]
+    pub registry: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub digest: Option<String>,
+}
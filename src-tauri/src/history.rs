@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::provider::UnifiedDeployment;
+
+// Deployments kept on disk; older ones are dropped when the file is compacted
+const MAX_DEPLOYMENTS: usize = 2000;
+// Rewrite the file once it holds this many lines per deployment kept
+const COMPACT_RATIO: usize = 4;
+
+/// A status a deployment was seen in, and when (unix ms)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusEntry {
+    pub status: String,
+    pub at: i64,
+}
+
+// One line of the history file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum HistoryLine {
+    // Latest fields of a deployment
+    Deployment { deployment: Box<UnifiedDeployment> },
+    Status { id: String, status: String, at: i64 },
+}
+
+struct HistoryRecord {
+    deployment: UnifiedDeployment,
+    statuses: Vec<StatusEntry>,
+}
+
+/// Every deployment seen and its status changes, kept in an append-only
+/// JSON lines file so history survives restarts and offline periods
+#[derive(Default)]
+pub struct DeploymentHistory {
+    path: Mutex<Option<PathBuf>>,
+    records: Mutex<HashMap<String, HistoryRecord>>,
+}
+
+impl DeploymentHistory {
+    /// Load the history file, creating it if needed. Until this is called,
+    /// history is only kept in memory.
+    pub fn open(&self, path: PathBuf) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut records = HashMap::new();
+        let mut lines = 0;
+
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                lines += 1;
+                // A crash mid-write can leave a partial last line
+                match serde_json::from_str::<HistoryLine>(&line) {
+                    Ok(entry) => apply(&mut records, entry),
+                    Err(e) => eprintln!("[HISTORY] Skipping unreadable line {}: {}", lines, e),
+                }
+            }
+        }
+
+        eprintln!("[HISTORY] Loaded {} deployments from {:?}", records.len(), path);
+
+        let needs_compaction = records.len() > MAX_DEPLOYMENTS || lines > records.len().max(1) * COMPACT_RATIO;
+        *self.records.lock().unwrap() = records;
+        *self.path.lock().unwrap() = Some(path);
+
+        if needs_compaction {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Record the latest state of some deployments, appending whatever
+    /// changed since they were last seen
+    pub fn record(&self, deployments: &[UnifiedDeployment]) {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut records = self.records.lock().unwrap();
+        let mut lines = Vec::new();
+
+        for d in deployments {
+            let (changed, status_changed) = match records.get(&d.id) {
+                Some(record) => (
+                    record.deployment != *d,
+                    record.statuses.last().is_none_or(|s| s.status != d.status),
+                ),
+                None => (true, true),
+            };
+
+            if changed {
+                lines.push(HistoryLine::Deployment { deployment: Box::new(d.clone()) });
+            }
+            if status_changed {
+                lines.push(HistoryLine::Status {
+                    id: d.id.clone(),
+                    status: d.status.clone(),
+                    at: now,
+                });
+            }
+        }
+
+        if lines.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self.append(&lines) {
+            eprintln!("[HISTORY] Failed to write history: {}", e);
+        }
+        for line in lines {
+            apply(&mut records, line);
+        }
+    }
+
+    /// Newest known deployments of the given accounts
+    pub fn latest(&self, account_ids: &[String], limit: usize) -> Vec<UnifiedDeployment> {
+        let records = self.records.lock().unwrap();
+        let mut deployments: Vec<UnifiedDeployment> = records
+            .values()
+            .filter(|r| r.deployment.account_id.as_ref().is_some_and(|id| account_ids.contains(id)))
+            .map(|r| r.deployment.clone())
+            .collect();
+
+        deployments.sort_by_key(|d| std::cmp::Reverse(d.created_at.unwrap_or(0)));
+        deployments.truncate(limit);
+        deployments
+    }
+
+    /// Last known state of a deployment
+    pub fn get(&self, deployment_id: &str) -> Option<UnifiedDeployment> {
+        self.records.lock().unwrap().get(deployment_id).map(|r| r.deployment.clone())
+    }
+
+    /// Account a provider's project was last seen under
+    pub fn account_for_project(&self, provider: &str, project_id: &str) -> Option<String> {
+        self.records
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|r| r.deployment.provider == provider && r.deployment.project_id.as_deref() == Some(project_id))
+            .max_by_key(|r| r.deployment.created_at.unwrap_or(0))
+            .and_then(|r| r.deployment.account_id.clone())
+    }
+
+    /// Every status a deployment was seen in, oldest first
+    pub fn status_history(&self, deployment_id: &str) -> Vec<StatusEntry> {
+        self.records
+            .lock()
+            .unwrap()
+            .get(deployment_id)
+            .map(|r| r.statuses.clone())
+            .unwrap_or_default()
+    }
+
+    /// Drop a removed account's deployments
+    pub fn forget_account(&self, account_id: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .retain(|_, r| r.deployment.account_id.as_deref() != Some(account_id));
+
+        if let Err(e) = self.compact() {
+            eprintln!("[HISTORY] Failed to compact history: {}", e);
+        }
+    }
+
+    fn append(&self, lines: &[HistoryLine]) -> Result<()> {
+        let path = match self.path.lock().unwrap().clone() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let mut file = BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?);
+        for line in lines {
+            serde_json::to_writer(&mut file, line)?;
+            file.write_all(b"\n")?;
+        }
+        file.flush()?;
+        Ok(())
+    }
+
+    // Rewrite the file with one snapshot per deployment, keeping the newest MAX_DEPLOYMENTS
+    fn compact(&self) -> Result<()> {
+        let path = match self.path.lock().unwrap().clone() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let mut records = self.records.lock().unwrap();
+        if records.len() > MAX_DEPLOYMENTS {
+            let mut created: Vec<i64> = records.values().map(|r| r.deployment.created_at.unwrap_or(0)).collect();
+            created.sort_unstable_by(|a, b| b.cmp(a));
+            let cutoff = created[MAX_DEPLOYMENTS - 1];
+            records.retain(|_, r| r.deployment.created_at.unwrap_or(0) >= cutoff);
+        }
+
+        // Write next to the file and swap, so a crash never loses the old one
+        let tmp = path.with_extension("jsonl.tmp");
+        let mut file = BufWriter::new(File::create(&tmp)?);
+        for record in records.values() {
+            serde_json::to_writer(&mut file, &HistoryLine::Deployment { deployment: Box::new(record.deployment.clone()) })?;
+            file.write_all(b"\n")?;
+            for s in &record.statuses {
+                let line = HistoryLine::Status {
+                    id: record.deployment.id.clone(),
+                    status: s.status.clone(),
+                    at: s.at,
+                };
+                serde_json::to_writer(&mut file, &line)?;
+                file.write_all(b"\n")?;
+            }
+        }
+        file.flush()?;
+        drop(file);
+        fs::rename(&tmp, &path)?;
+
+        eprintln!("[HISTORY] Compacted history to {} deployments", records.len());
+        Ok(())
+    }
+}
+
+fn apply(records: &mut HashMap<String, HistoryRecord>, line: HistoryLine) {
+    match line {
+        HistoryLine::Deployment { deployment } => {
+            // Lines written before deployments carried their links
+            let deployment = deployment.with_links();
+            match records.get_mut(&deployment.id) {
+                Some(record) => record.deployment = deployment,
+                None => {
+                    records.insert(
+                        deployment.id.clone(),
+                        HistoryRecord { deployment, statuses: Vec::new() },
+                    );
+                }
+            }
+        }
+        HistoryLine::Status { id, status, at } => {
+            if let Some(record) = records.get_mut(&id) {
+                record.statuses.push(StatusEntry { status, at });
+            }
+        }
+    }
+}
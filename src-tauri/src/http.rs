@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::Result;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use crate::error::AppError;
+
+// Attempts per request, including the first one
+const MAX_ATTEMPTS: u32 = 4;
+// First retry delay, doubled on every further attempt
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+// Longest we wait for a rate limit to lift before giving up
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+// Start spacing out polls once less than this share of the quota is left
+const LOW_QUOTA_RATIO: f64 = 0.2;
+
+/// Last known quota of a token, as reported by the provider
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitState {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    // Unix seconds when the quota resets
+    pub reset_at: Option<i64>,
+    // Unix seconds until which the provider rejects requests
+    pub limited_until: Option<i64>,
+}
+
+impl RateLimitState {
+    pub fn is_limited(&self) -> bool {
+        self.limited_until.is_some_and(|until| until > now())
+    }
+}
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Quota of one token, shared by every client made for its account
+#[derive(Debug, Clone, Default)]
+pub struct Quota(Arc<Mutex<Option<RateLimitState>>>);
+
+impl Quota {
+    /// Rate limit state of the token, if any request reported one
+    pub fn state(&self) -> Option<RateLimitState> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Record that the token is rate limited, e.g. from a GraphQL error
+    pub fn mark_limited(&self, retry_after: Option<u64>) {
+        let wait = retry_after.unwrap_or(MAX_RETRY_WAIT.as_secs());
+        let mut state = self.0.lock().unwrap();
+        let state = state.get_or_insert_with(Default::default);
+        state.limited_until = Some(now() + wait as i64);
+        state.remaining = Some(0);
+    }
+
+    /// How long pollers should wait before using this token again.
+    ///
+    /// While limited, that's until the limit lifts. When the quota runs low, the
+    /// remaining requests are spread over the time left until it resets.
+    pub fn throttle_delay(&self) -> Option<Duration> {
+        let state = self.state()?;
+        let now = now();
+
+        if let Some(until) = state.limited_until.filter(|until| *until > now) {
+            return Some(Duration::from_secs((until - now) as u64));
+        }
+
+        let (limit, remaining, reset_at) = (state.limit?, state.remaining?, state.reset_at?);
+        if reset_at <= now || limit == 0 || (remaining as f64) >= limit as f64 * LOW_QUOTA_RATIO {
+            return None;
+        }
+
+        let seconds_left = (reset_at - now) as u64;
+        Some(Duration::from_secs(seconds_left / remaining.max(1)))
+    }
+
+    // Update from `X-RateLimit-*` (Vercel, Netlify) or `RateLimit-*` (Render) headers
+    fn record(&self, status: StatusCode, headers: &HeaderMap) {
+        let limit = header_u64(headers, "x-ratelimit-limit").or_else(|| header_u64(headers, "ratelimit-limit"));
+        let remaining = header_u64(headers, "x-ratelimit-remaining").or_else(|| header_u64(headers, "ratelimit-remaining"));
+        let reset = header_u64(headers, "x-ratelimit-reset").or_else(|| header_u64(headers, "ratelimit-reset"));
+        let limited = status == StatusCode::TOO_MANY_REQUESTS;
+
+        if limit.is_none() && remaining.is_none() && reset.is_none() && !limited {
+            return;
+        }
+
+        let now = now();
+        // Reset is either a unix timestamp or seconds from now
+        let reset_at = reset.map(|r| if r > 1_000_000_000 { r as i64 } else { now + r as i64 });
+
+        let mut state = self.0.lock().unwrap();
+        let state = state.get_or_insert_with(Default::default);
+        state.limit = limit.or(state.limit);
+        state.remaining = remaining.or(state.remaining);
+        state.reset_at = reset_at.or(state.reset_at);
+
+        if limited {
+            let wait = header_u64(headers, "retry-after")
+                .map(|s| now + s as i64)
+                .or(reset_at)
+                .unwrap_or(now + MAX_RETRY_WAIT.as_secs() as i64);
+            state.limited_until = Some(wait);
+        } else if status.is_success() {
+            state.limited_until = None;
+        }
+    }
+}
+
+/// Sending through the shared rate-limit layer
+pub trait TrackedSend {
+    /// Send, tracking the token's quota and retrying GET/PUT/DELETE on rate
+    /// limits, server errors and connection failures
+    fn send_tracked(self, quota: &Quota) -> impl Future<Output = Result<Response>> + Send;
+
+    /// Like `send_tracked`, but also retries POSTs, for read-only GraphQL queries
+    fn send_query(self, quota: &Quota) -> impl Future<Output = Result<Response>> + Send;
+}
+
+impl TrackedSend for RequestBuilder {
+    fn send_tracked(self, quota: &Quota) -> impl Future<Output = Result<Response>> + Send {
+        send_with(self, quota, None)
+    }
+
+    fn send_query(self, quota: &Quota) -> impl Future<Output = Result<Response>> + Send {
+        send_with(self, quota, Some(true))
+    }
+}
+
+async fn send_with(req: RequestBuilder, quota: &Quota, idempotent: Option<bool>) -> Result<Response> {
+    let (client, request) = req.build_split();
+    let request = request?;
+    let idempotent = idempotent.unwrap_or_else(|| is_idempotent(request.method()));
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+
+        // Don't hit a limit we already know about
+        if let Some(state) = quota.state().filter(|s| s.is_limited()) {
+            let wait = (state.limited_until.unwrap_or(0) - now()).max(0) as u64;
+            if !idempotent || Duration::from_secs(wait) > MAX_RETRY_WAIT {
+                return Err(AppError::RateLimited {
+                    retry_after: Some(wait),
+                    message: "Rate limited".to_string(),
+                }
+                .into());
+            }
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+        }
+
+        // Bodies that can't be cloned (streams) only get one attempt
+        let retryable = idempotent && attempt < MAX_ATTEMPTS;
+        let current = match request.try_clone() {
+            Some(clone) if retryable => clone,
+            _ => return execute(&client, request, quota).await,
+        };
+
+        match execute(&client, current, quota).await {
+            Ok(resp) if should_retry(resp.status()) => {
+                let delay = retry_delay(attempt, resp.headers());
+                if delay > MAX_RETRY_WAIT {
+                    return Ok(resp);
+                }
+                eprintln!("[HTTP] {} from {}, retrying in {:?}", resp.status(), resp.url(), delay);
+                tokio::time::sleep(delay).await;
+            }
+            Ok(resp) => return Ok(resp),
+            Err(e) if is_transient(&e) => {
+                let delay = backoff(attempt);
+                eprintln!("[HTTP] {}, retrying in {:?}", e, delay);
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+async fn execute(client: &reqwest::Client, request: reqwest::Request, quota: &Quota) -> Result<Response> {
+    let resp = client.execute(request).await?;
+    quota.record(resp.status(), resp.headers());
+    Ok(resp)
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+}
+
+fn should_retry(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::BAD_GATEWAY
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || status == StatusCode::GATEWAY_TIMEOUT
+}
+
+fn is_transient(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_timeout() || e.is_connect())
+}
+
+// Retry-After when the provider sends one, otherwise exponential backoff
+fn retry_delay(attempt: u32, headers: &HeaderMap) -> Duration {
+    header_u64(headers, "retry-after")
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| backoff(attempt))
+}
+
+/// Exponential backoff with up to 50% jitter, so clients don't retry in lockstep
+pub fn backoff(attempt: u32) -> Duration {
+    let base = BASE_BACKOFF.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let jitter = base.mul_f64((nanos % 1000) as f64 / 2000.0);
+    base + jitter
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota_with(state: RateLimitState) -> Quota {
+        Quota(Arc::new(Mutex::new(Some(state))))
+    }
+
+    fn quota(limit: u64, remaining: u64, resets_in: i64) -> RateLimitState {
+        RateLimitState {
+            limit: Some(limit),
+            remaining: Some(remaining),
+            reset_at: Some(now() + resets_in),
+            limited_until: None,
+        }
+    }
+
+    #[test]
+    fn quota_is_shared_between_clones() {
+        let quota = Quota::default();
+        quota.clone().mark_limited(Some(30));
+        assert!(quota.state().is_some_and(|s| s.is_limited()));
+    }
+
+    #[test]
+    fn throttle_delay_is_none_without_a_known_quota() {
+        assert_eq!(Quota::default().throttle_delay(), None);
+
+        let partial = quota_with(RateLimitState { remaining: Some(1), ..Default::default() });
+        assert_eq!(partial.throttle_delay(), None);
+    }
+
+    #[test]
+    fn throttle_delay_waits_out_a_rate_limit() {
+        let limited = quota_with(RateLimitState { limited_until: Some(now() + 30), ..quota(100, 0, 600) });
+
+        let delay = limited.throttle_delay().unwrap().as_secs();
+        assert!((29..=30).contains(&delay), "delay was {}", delay);
+    }
+
+    #[test]
+    fn throttle_delay_ignores_an_expired_rate_limit() {
+        let expired = quota_with(RateLimitState { limited_until: Some(now() - 5), ..quota(100, 90, 600) });
+        assert_eq!(expired.throttle_delay(), None);
+    }
+
+    #[test]
+    fn throttle_delay_is_none_with_plenty_of_quota_left() {
+        assert_eq!(quota_with(quota(100, 20, 600)).throttle_delay(), None);
+    }
+
+    #[test]
+    fn throttle_delay_spreads_a_low_quota_until_reset() {
+        let delay = quota_with(quota(100, 10, 600)).throttle_delay().unwrap().as_secs();
+        assert!((59..=60).contains(&delay), "delay was {}", delay);
+    }
+
+    #[test]
+    fn throttle_delay_handles_an_empty_or_reset_quota() {
+        let delay = quota_with(quota(100, 0, 120)).throttle_delay().unwrap().as_secs();
+        assert!((119..=120).contains(&delay), "delay was {}", delay);
+
+        assert_eq!(quota_with(quota(100, 0, -1)).throttle_delay(), None);
+        assert_eq!(quota_with(quota(0, 0, 600)).throttle_delay(), None);
+    }
+}
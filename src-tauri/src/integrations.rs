@@ -0,0 +1,201 @@
+use std::sync::Mutex;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::rules::WebhookTargets;
+use crate::tracker::{DeploymentPhase, DeploymentStatusChange};
+
+/// Chat service an integration posts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrationKind {
+    Slack,
+    Discord,
+}
+
+/// An outbound webhook that gets a message for every deployment status change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Integration {
+    pub id: String,
+    pub kind: IntegrationKind,
+    pub name: String,
+    // Slack incoming webhook or Discord webhook URL; the URL is the credential
+    pub webhook_url: String,
+    pub enabled: bool,
+}
+
+/// Configured integrations, stored in the keychain with the accounts
+#[derive(Default)]
+pub struct Integrations {
+    list: Mutex<Vec<Integration>>,
+    client: reqwest::Client,
+}
+
+impl Integrations {
+    pub fn list(&self) -> Vec<Integration> {
+        self.list.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, integrations: Vec<Integration>) {
+        *self.list.lock().unwrap() = integrations;
+    }
+
+    pub fn get(&self, id: &str) -> Option<Integration> {
+        self.list.lock().unwrap().iter().find(|i| i.id == id).cloned()
+    }
+
+    /// Add an integration or replace the one with the same ID
+    pub fn upsert(&self, integration: Integration) {
+        let mut list = self.list.lock().unwrap();
+        match list.iter_mut().find(|i| i.id == integration.id) {
+            Some(existing) => *existing = integration,
+            None => list.push(integration),
+        }
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.list.lock().unwrap().retain(|i| i.id != id);
+    }
+
+    /// Post a change to the targeted enabled integrations in the background
+    pub fn publish(&self, change: &DeploymentStatusChange, targets: &WebhookTargets) {
+        let integrations = self.list().into_iter().filter(|i| i.enabled && targets.includes(&i.id));
+
+        for integration in integrations {
+            let client = self.client.clone();
+            let change = change.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = post(&client, &integration, &change).await {
+                    eprintln!("[INTEGRATIONS] {} failed: {}", integration.name, e);
+                }
+            });
+        }
+    }
+
+    /// Post a change to one integration and wait for the result
+    pub async fn send(&self, integration: &Integration, change: &DeploymentStatusChange) -> Result<()> {
+        post(&self.client, integration, change).await
+    }
+}
+
+async fn post(client: &reqwest::Client, integration: &Integration, change: &DeploymentStatusChange) -> Result<()> {
+    let body = match integration.kind {
+        IntegrationKind::Slack => slack_message(change),
+        IntegrationKind::Discord => discord_message(change),
+    };
+
+    let resp = client.post(&integration.webhook_url).json(&body).send().await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(anyhow!("{} - {}", status, text));
+    }
+    Ok(())
+}
+
+fn color(phase: DeploymentPhase) -> u32 {
+    match phase {
+        DeploymentPhase::Ready => 0x22c55e,
+        DeploymentPhase::Error => 0xef4444,
+        DeploymentPhase::Queued | DeploymentPhase::Building => 0xeab308,
+        DeploymentPhase::Canceled | DeploymentPhase::Unknown => 0x525252,
+    }
+}
+
+// Label and value of each detail shown, skipping unknown ones
+fn fields(change: &DeploymentStatusChange) -> Vec<(&'static str, String)> {
+    let d = &change.deployment;
+    let mut fields = vec![("Project", d.name.clone())];
+    if let Some(branch) = &d.branch {
+        fields.push(("Branch", branch.clone()));
+    }
+    if let Some(author) = &d.git_author_login {
+        fields.push(("Author", author.clone()));
+    }
+    fields.push(("Status", d.status.clone()));
+    if let Some(ms) = d.build_ms {
+        fields.push(("Duration", format_duration(ms)));
+    }
+    fields
+}
+
+fn links(change: &DeploymentStatusChange) -> Vec<(&'static str, String)> {
+    let d = &change.deployment;
+    [("Dashboard", d.dashboard_url.clone()), ("Logs", d.logs_url.clone()), ("Visit", d.url.clone())]
+        .into_iter()
+        .filter_map(|(label, url)| url.map(|url| (label, url)))
+        .collect()
+}
+
+fn slack_message(change: &DeploymentStatusChange) -> Value {
+    let d = &change.deployment;
+    let title = change.title();
+    let commit = d.commit_message.clone().unwrap_or_else(|| "No commit message".to_string());
+
+    let fields: Vec<Value> = fields(change)
+        .into_iter()
+        .map(|(label, value)| json!({ "type": "mrkdwn", "text": format!("*{}*\n{}", label, value) }))
+        .collect();
+    let links = links(change)
+        .into_iter()
+        .map(|(label, url)| format!("<{}|{}>", url, label))
+        .collect::<Vec<_>>()
+        .join("  ·  ");
+
+    let mut blocks = vec![
+        json!({ "type": "section", "text": { "type": "mrkdwn", "text": format!("*{}*\n{}", title, commit) } }),
+        json!({ "type": "section", "fields": fields }),
+    ];
+    if !links.is_empty() {
+        blocks.push(json!({ "type": "context", "elements": [{ "type": "mrkdwn", "text": links }] }));
+    }
+
+    json!({
+        // Shown in push notifications
+        "text": title,
+        "attachments": [{
+            "color": format!("#{:06x}", color(change.current)),
+            "blocks": blocks
+        }]
+    })
+}
+
+fn discord_message(change: &DeploymentStatusChange) -> Value {
+    let d = &change.deployment;
+
+    let mut fields: Vec<Value> = fields(change)
+        .into_iter()
+        .map(|(label, value)| json!({ "name": label, "value": value, "inline": true }))
+        .collect();
+    let links = links(change)
+        .into_iter()
+        .map(|(label, url)| format!("[{}]({})", label, url))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    if !links.is_empty() {
+        fields.push(json!({ "name": "Links", "value": links, "inline": false }));
+    }
+
+    let mut embed = json!({
+        "title": change.title(),
+        "description": d.commit_message.clone().unwrap_or_else(|| "No commit message".to_string()),
+        "color": color(change.current),
+        "fields": fields,
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    });
+    if let Some(url) = &d.logs_url {
+        embed["url"] = json!(url);
+    }
+
+    json!({ "embeds": [embed] })
+}
+
+fn format_duration(ms: i64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}h {}m", s / 3600, (s % 3600) / 60),
+    }
+}
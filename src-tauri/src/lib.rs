@@ -1,26 +1,27 @@
+mod analytics;
+mod cloudflare;
 mod commands;
+mod error;
+mod fly;
+mod history;
+mod http;
+mod integrations;
+mod netlify;
+mod provider;
 mod railway;
+mod render;
+mod rules;
+mod scheduler;
 mod state;
+mod tracker;
 mod tray;
 mod vercel;
+mod webhook;
 
 use commands::*;
 use state::AppState;
 use tauri::Manager;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::runtime::Runtime;
-
-// Global flag to signal background thread that a build is in progress
-static IS_BUILDING: AtomicBool = AtomicBool::new(false);
-
-pub fn set_building_flag(building: bool) {
-    IS_BUILDING.store(building, Ordering::Relaxed);
-}
-
-pub fn get_building_flag() -> bool {
-    IS_BUILDING.load(Ordering::Relaxed)
-}
 
 #[cfg(target_os = "macos")]
 use tauri_nspanel::{
@@ -51,75 +52,27 @@ pub fn run() {
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
 
-            // Background thread to poll for build status when a build is detected
-            let app_handle = app.handle().clone();
-            std::thread::spawn(move || {
-                let rt = Runtime::new().unwrap();
-                rt.block_on(async {
-                    loop {
-                        // Only poll when building flag is set
-                        if get_building_flag() {
-                            eprintln!("[BG] Building flag is set, checking status...");
-                            let state = app_handle.state::<AppState>();
-
-                            if state.is_initialized() {
-                                let accounts = state.get_all_accounts();
-                                let mut has_building = false;
-
-                                'outer: for account in accounts {
-                                    match account.provider.as_str() {
-                                        "vercel" => {
-                                            if let Ok(client) = vercel::create_client(&account.token) {
-                                                if let Ok(deployments) = client.list_deployments(None, 5).await {
-                                                    for d in deployments {
-                                                        if let Some(s) = d.state.as_ref().or(d.ready_state.as_ref()) {
-                                                            let status = format!("{:?}", s).to_uppercase();
-                                                            eprintln!("[BG] Vercel deployment status: {}", status);
-                                                            if status == "BUILDING" || status == "QUEUED" || status == "INITIALIZING" {
-                                                                has_building = true;
-                                                                break 'outer;
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        "railway" => {
-                                            let token_type = if account.scope_type == "project" { "project" } else { "workspace" };
-                                            if let Ok(client) = railway::create_client_with_type(&account.token, token_type) {
-                                                if let Ok(deployments) = client.list_deployments(None, None, None, 5).await {
-                                                    for d in deployments {
-                                                        let status = format!("{:?}", d.status).to_uppercase();
-                                                        eprintln!("[BG] Railway deployment status: {}", status);
-                                                        if status == "BUILDING" || status == "DEPLOYING" || status == "INITIALIZING" {
-                                                            has_building = true;
-                                                            break 'outer;
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                }
-
-                                eprintln!("[BG] has_building = {}", has_building);
-                                // Build finished - clear tray
-                                if !has_building {
-                                    eprintln!("[BG] Clearing tray text!");
-                                    set_building_flag(false);
-                                    tray::set_tray_normal(&app_handle);
-                                }
-                            }
-
-                            // Poll every 10s while building
-                            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-                        } else {
-                            // Not building - just sleep and check flag periodically
-                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                        }
+            // Load deployment history before anything reads it
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    if let Err(e) = app.state::<AppState>().history.open(dir.join("history.jsonl")) {
+                        eprintln!("[HISTORY] Failed to open history: {}", e);
                     }
-                });
+                }
+                Err(e) => eprintln!("[HISTORY] No app data dir: {}", e),
+            }
+
+            // Poll every account in the background, even while the webview is closed
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app_handle.state::<AppState>();
+                if let Err(e) = initialize_state(&state).await {
+                    eprintln!("[SCHEDULER] Failed to load accounts: {}", e);
+                }
+                state.scheduler.start(app_handle.clone(), &state.get_all_accounts());
+                if let Err(e) = state.webhook.restart(app_handle.clone()).await {
+                    eprintln!("[WEBHOOK] {}", e);
+                }
             });
 
             // Set up window with vibrancy and convert to panel for fullscreen support
@@ -183,6 +136,10 @@ pub fn run() {
             // Multi-account commands
             add_account,
             add_railway_account,
+            add_netlify_account,
+            add_render_account,
+            add_fly_account,
+            add_cloudflare_account,
             list_accounts,
             get_account_token,
             remove_account,
@@ -191,19 +148,47 @@ pub fn run() {
             set_active_account,
             get_current_account,
             open_railway_tokens,
+            open_netlify_tokens,
+            open_render_tokens,
+            open_fly_tokens,
+            open_cloudflare_tokens,
+            list_vercel_teams,
+            set_vercel_teams,
             // Vercel deployment commands
             list_projects,
             list_deployments,
             get_deployment,
+            cancel_deployment,
+            redeploy_deployment,
+            promote_deployment,
+            rollback_deployment,
             // Unified deployments (all providers)
             list_all_deployments,
+            load_more_deployments,
+            get_status_history,
+            get_build_stats,
+            get_webhook_config,
+            set_webhook_config,
+            list_integrations,
+            save_integration,
+            remove_integration,
+            test_integration,
+            get_notification_settings,
+            set_notification_settings,
+            get_rate_limits,
             update_tray_status,
+            acknowledge_tray_failures,
             send_deployment_notification,
             // Railway deployment commands
             railway_verify_token,
             railway_list_projects,
             railway_list_deployments,
             railway_get_deployment,
+            railway_redeploy_deployment,
+            railway_restart_deployment,
+            railway_rollback_deployment,
+            railway_cancel_deployment,
+            railway_remove_deployment,
             // Log commands
             stream_deployment_logs,
             fetch_deployment_logs,
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
+use crate::provider;
+use crate::vercel::LogLine;
+use super::types::*;
+
+const API_BASE: &str = "https://api.netlify.com/api/v1";
+// Sites polled per account; each costs one deploys request per poll
+const MAX_SITES: u32 = 20;
+
+pub struct NetlifyClient {
+    client: reqwest::Client,
+    token: String,
+    quota: Quota,
+}
+
+impl NetlifyClient {
+    pub fn new(token: String) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token))?,
+        );
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self { client, token, quota: Quota::default() })
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub async fn get_user(&self) -> Result<NetlifyUser> {
+        let resp = self
+            .client
+            .get(format!("{}/user", API_BASE))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to get user", resp).await.into());
+        }
+
+        let user: NetlifyUser = resp.json().await?;
+        Ok(user)
+    }
+
+    /// List sites, most recently updated first
+    pub async fn list_sites(&self, limit: u32) -> Result<Vec<NetlifySite>> {
+        let resp = self
+            .client
+            .get(format!("{}/sites", API_BASE))
+            .query(&[
+                ("filter", "all".to_string()),
+                ("sort_by", "updated_at".to_string()),
+                ("per_page", limit.to_string()),
+            ])
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list sites", resp).await.into());
+        }
+
+        let sites: Vec<NetlifySite> = resp.json().await?;
+        Ok(sites)
+    }
+
+    pub async fn list_deploys(&self, site_id: &str, limit: u32) -> Result<Vec<NetlifyDeploy>> {
+        let resp = self
+            .client
+            .get(format!("{}/sites/{}/deploys", API_BASE, site_id))
+            .query(&[("per_page", limit.to_string())])
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list deploys", resp).await.into());
+        }
+
+        let deploys: Vec<NetlifyDeploy> = resp.json().await?;
+        Ok(deploys)
+    }
+
+    /// Latest deploys across the `MAX_SITES` most recently updated sites
+    pub async fn list_all_deploys(&self, limit: u32) -> Result<Vec<NetlifyDeploy>> {
+        let sites = self.list_sites(MAX_SITES).await?;
+
+        // Fetch each site's deploys concurrently
+        let results = futures::future::join_all(
+            sites.iter().map(|site| self.list_deploys(&site.id, limit)),
+        )
+        .await;
+
+        let mut all_deploys = provider::merge_listings("NETLIFY", results)?;
+
+        // Sort by created_at descending (most recent first)
+        all_deploys.sort_by(|a, b| {
+            let a_time = a.created_at.as_deref().unwrap_or("");
+            let b_time = b.created_at.as_deref().unwrap_or("");
+            b_time.cmp(a_time)
+        });
+
+        all_deploys.truncate(limit as usize);
+
+        Ok(all_deploys)
+    }
+
+    pub async fn get_deploy(&self, deploy_id: &str) -> Result<NetlifyDeploy> {
+        let resp = self
+            .client
+            .get(format!("{}/deploys/{}", API_BASE, deploy_id))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to get deploy", resp).await.into());
+        }
+
+        let deploy: NetlifyDeploy = resp.json().await?;
+        Ok(deploy)
+    }
+
+    /// Fetch the build log of a deploy.
+    ///
+    /// Netlify doesn't serve build logs from the REST API; the deploy's
+    /// `log_access_attributes` point at a Firebase location readable with the
+    /// token it carries.
+    pub async fn get_deploy_logs(&self, deploy_id: &str) -> Result<Vec<LogLine>> {
+        let deploy = self.get_deploy(deploy_id).await?;
+
+        let attrs = deploy
+            .log_access_attributes
+            .ok_or_else(|| anyhow!("No build log available for deploy {}", deploy_id))?;
+
+        let endpoint = attrs
+            .endpoint
+            .or(attrs.url)
+            .ok_or_else(|| anyhow!("No build log endpoint for deploy {}", deploy_id))?;
+        let path = attrs.path.unwrap_or_default();
+
+        let mut req = reqwest::Client::new()
+            .get(format!("{}{}.json", endpoint.trim_end_matches('/'), path));
+        if let Some(token) = attrs.token {
+            req = req.query(&[("auth", token)]);
+        }
+
+        let resp = req.send().await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to fetch logs", resp).await.into());
+        }
+
+        // Entries are keyed by push ID, which sorts chronologically
+        let entries: Option<std::collections::BTreeMap<String, NetlifyLogEntry>> = resp.json().await?;
+
+        let logs = entries
+            .unwrap_or_default()
+            .into_values()
+            .filter_map(|entry| {
+                let text = entry.message?;
+                let lower = text.to_lowercase();
+                Some(LogLine {
+                    timestamp: entry.ts.unwrap_or(0),
+                    is_error: lower.contains("error") || lower.contains("failed"),
+                    text,
+                })
+            })
+            .collect();
+
+        Ok(logs)
+    }
+}
+
+pub fn create_client(token: &str) -> Result<NetlifyClient> {
+    NetlifyClient::new(token.to_string())
+}
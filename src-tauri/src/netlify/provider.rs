@@ -0,0 +1,71 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crate::provider::{self, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use crate::vercel::LogLine;
+use super::client::{create_client, NetlifyClient};
+use super::types::*;
+
+impl From<NetlifyDeploy> for UnifiedDeployment {
+    fn from(d: NetlifyDeploy) -> Self {
+        UnifiedDeployment {
+            id: d.id.clone(),
+            provider: "netlify".to_string(),
+            name: d.name.clone().unwrap_or_else(|| "Deploy".to_string()),
+            url: d.deploy_ssl_url.clone().or(d.deploy_url.clone()),
+            status: d.state.to_unified().to_string(),
+            created_at: provider::parse_timestamp(d.created_at.as_deref()),
+            commit_message: d.title.clone(),
+            branch: d.branch.clone(),
+            project_id: Some(d.site_id.clone()),
+            service_id: None,
+            git_author_login: d.committer.clone(),
+            team_slug: None,  // Not used for Netlify
+            account_id: None,
+            queue_ms: None,
+            build_ms: d.deploy_time.map(|s| s * 1000),
+            environment: d.context.clone(),
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+#[async_trait]
+impl DeploymentProvider for NetlifyClient {
+    async fn validate_token(&self) -> Result<()> {
+        self.get_user().await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let sites = self.list_sites(100).await?;
+        Ok(sites
+            .into_iter()
+            .map(|s| UnifiedProject {
+                updated_at: provider::parse_timestamp(s.updated_at.as_deref()),
+                id: s.id,
+                provider: "netlify".to_string(),
+                name: s.name,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let deploys = self.list_all_deploys(limit).await?;
+        Ok(deploys.into_iter().map(UnifiedDeployment::from).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let deploy = self.get_deploy(deployment_id).await?;
+        Ok(deploy.into())
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        self.get_deploy_logs(deployment_id).await
+    }
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    Ok(Box::new(create_client(&account.token)?.with_quota(account.quota.clone())))
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetlifyUser {
+    pub id: String,
+    #[serde(default)]
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetlifySite {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub ssl_url: Option<String>,
+    #[serde(default)]
+    pub admin_url: Option<String>,
+    #[serde(default)]
+    pub account_slug: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetlifyDeployState {
+    New,
+    PendingReview,
+    Accepted,
+    Rejected,
+    Enqueued,
+    Building,
+    Uploading,
+    Uploaded,
+    Preparing,
+    Prepared,
+    Processing,
+    Processed,
+    Retrying,
+    Ready,
+    Error,
+    #[serde(other)]
+    Unknown,
+}
+
+impl NetlifyDeployState {
+    /// Convert Netlify state to unified status string for frontend
+    pub fn to_unified(&self) -> &'static str {
+        match self {
+            NetlifyDeployState::New => "QUEUED",
+            NetlifyDeployState::PendingReview => "QUEUED",
+            NetlifyDeployState::Accepted => "QUEUED",
+            NetlifyDeployState::Enqueued => "QUEUED",
+            NetlifyDeployState::Building => "BUILDING",
+            NetlifyDeployState::Uploading => "BUILDING",
+            NetlifyDeployState::Uploaded => "BUILDING",
+            NetlifyDeployState::Preparing => "BUILDING",
+            NetlifyDeployState::Prepared => "BUILDING",
+            NetlifyDeployState::Processing => "BUILDING",
+            NetlifyDeployState::Processed => "BUILDING",
+            NetlifyDeployState::Retrying => "BUILDING",
+            NetlifyDeployState::Ready => "READY",
+            NetlifyDeployState::Rejected => "CANCELED",
+            NetlifyDeployState::Error => "ERROR",
+            NetlifyDeployState::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetlifyDeploy {
+    pub id: String,
+    pub site_id: String,
+    pub state: NetlifyDeployState,
+    /// Site name
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub commit_ref: Option<String>,
+    #[serde(default)]
+    pub commit_url: Option<String>,
+    /// Commit message for git-triggered deploys
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub committer: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub deploy_url: Option<String>,
+    #[serde(default)]
+    pub deploy_ssl_url: Option<String>,
+    #[serde(default)]
+    pub error_message: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    // Build time in seconds, set once the deploy is done
+    #[serde(default)]
+    pub deploy_time: Option<i64>,
+    #[serde(default)]
+    pub log_access_attributes: Option<LogAccessAttributes>,
+}
+
+/// Where the build log for a deploy can be read from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogAccessAttributes {
+    #[serde(rename = "type", default)]
+    pub access_type: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetlifyLogEntry {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub ts: Option<i64>,
+}
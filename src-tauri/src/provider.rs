@@ -0,0 +1,306 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::cloudflare;
+use crate::error::AppError;
+use crate::fly;
+use crate::netlify;
+use crate::railway;
+use crate::render;
+use crate::state::CachedAccount;
+use crate::vercel::{self, LogLine};
+
+/// Unified deployment type that works across providers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnifiedDeployment {
+    pub id: String,
+    pub provider: String,  // Matches CachedAccount.provider
+    pub name: String,
+    pub url: Option<String>,
+    pub status: String,
+    pub created_at: Option<i64>,  // Unix timestamp in ms
+    pub commit_message: Option<String>,
+    pub branch: Option<String>,
+    pub project_id: Option<String>,
+    pub service_id: Option<String>,
+    pub git_author_login: Option<String>,  // GitHub username for avatar
+    pub team_slug: Option<String>,  // Team/user slug for Vercel dashboard URLs
+    pub account_id: Option<String>,  // Account ID for fetching logs
+    pub queue_ms: Option<i64>,  // Time from creation until the build started
+    pub build_ms: Option<i64>,  // Time the build took, once finished
+    pub environment: Option<String>,  // e.g. "production" or "preview", where the provider says
+    #[serde(default)]
+    pub dashboard_url: Option<String>,  // Provider dashboard page of the project
+    #[serde(default)]
+    pub logs_url: Option<String>,  // Provider page showing this deployment's build logs
+}
+
+impl UnifiedDeployment {
+    /// Whether the deployment is still queued or in progress
+    pub fn is_building(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "BUILDING" | "DEPLOYING" | "INITIALIZING" | "QUEUED"
+        )
+    }
+
+    /// Fill in the dashboard and logs links the provider didn't set
+    pub fn with_links(mut self) -> Self {
+        if self.dashboard_url.is_none() {
+            self.dashboard_url = self.default_dashboard_url();
+        }
+        if self.logs_url.is_none() {
+            self.logs_url = self.default_logs_url();
+        }
+        self
+    }
+
+    fn default_dashboard_url(&self) -> Option<String> {
+        let project = self.project_id.as_deref();
+        match self.provider.as_str() {
+            "vercel" => Some(format!("https://vercel.com/{}/{}", self.team_slug.as_deref()?, self.name)),
+            "railway" => Some(format!("https://railway.com/project/{}", project?)),
+            "netlify" => Some(format!("https://app.netlify.com/sites/{}", self.name)),
+            "fly" => Some(format!("https://fly.io/apps/{}", project?)),
+            // `:account` makes the dashboard pick the signed-in account
+            "cloudflare" => Some(format!("https://dash.cloudflare.com/?to=/:account/pages/view/{}", project?)),
+            _ => None,
+        }
+    }
+
+    fn default_logs_url(&self) -> Option<String> {
+        let dashboard = self.dashboard_url.as_deref();
+        let project = self.project_id.as_deref();
+        match self.provider.as_str() {
+            "vercel" => Some(format!("{}/{}", dashboard?, self.id)),
+            "railway" => match self.service_id.as_deref() {
+                Some(service) => Some(format!("https://railway.com/project/{}/service/{}?id={}", project?, service, self.id)),
+                None => dashboard.map(str::to_string),
+            },
+            "netlify" | "render" => Some(format!("{}/deploys/{}", dashboard?, self.id)),
+            "fly" => Some(format!("{}/monitoring", dashboard?)),
+            // Cloudflare ids are "project/deployment"
+            "cloudflare" => Some(format!("{}/{}", dashboard?, self.id.rsplit('/').next()?)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl UnifiedDeployment {
+    /// A ready Vercel deployment with every optional field empty, for tests
+    /// to fill in what they need
+    pub fn test_default() -> Self {
+        UnifiedDeployment {
+            id: "dpl_test".to_string(),
+            provider: "vercel".to_string(),
+            name: "web".to_string(),
+            url: None,
+            status: "READY".to_string(),
+            created_at: None,
+            commit_message: None,
+            branch: None,
+            project_id: None,
+            service_id: None,
+            git_author_login: None,
+            team_slug: None,
+            account_id: None,
+            queue_ms: None,
+            build_ms: None,
+            environment: None,
+            dashboard_url: None,
+            logs_url: None,
+        }
+    }
+}
+
+/// Unified project type that works across providers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnifiedProject {
+    pub id: String,
+    pub provider: String,
+    pub name: String,
+    pub updated_at: Option<i64>,  // Unix timestamp in ms
+}
+
+/// A page of deployments and the cursor for older ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentPage {
+    pub deployments: Vec<UnifiedDeployment>,
+    // Pass back to fetch the next page, None when there is no more history
+    pub next_cursor: Option<String>,
+}
+
+/// Common operations every deployment platform has to support
+#[async_trait]
+pub trait DeploymentProvider: Send + Sync {
+    /// Check that the token is accepted by the provider
+    async fn validate_token(&self) -> Result<()>;
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>>;
+
+    /// Most recent deployments across all projects, newest first
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>>;
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment>;
+
+    /// Deployments older than `cursor`, a previous page's `next_cursor`.
+    ///
+    /// Providers without history paging only serve the first page.
+    async fn list_deployments_page(&self, limit: u32, cursor: Option<String>) -> Result<DeploymentPage> {
+        match cursor {
+            None => Ok(DeploymentPage {
+                deployments: self.list_deployments(limit).await?,
+                next_cursor: None,
+            }),
+            Some(_) => Err(anyhow!("Loading older deployments is not supported")),
+        }
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>>;
+
+    /// Follow a deployment's logs until it finishes.
+    ///
+    /// Providers without a log stream re-fetch the logs while the deployment
+    /// is still building and pass on the new lines.
+    async fn stream_logs(&self, deployment_id: &str, on_log: &mut (dyn FnMut(LogLine) + Send)) -> Result<()> {
+        let mut emitted = 0;
+        loop {
+            // Check status first so lines written before the build finished are still emitted
+            let is_building = self
+                .get_deployment(deployment_id)
+                .await
+                .map(|d| d.is_building())
+                .unwrap_or(false);
+
+            let logs = self.fetch_logs(deployment_id).await?;
+            let total = logs.len();
+            for line in logs.into_iter().skip(emitted) {
+                on_log(line);
+            }
+            emitted = emitted.max(total);
+
+            if !is_building {
+                return Ok(());
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(LOG_POLL_INTERVAL_SECS)).await;
+        }
+    }
+}
+
+// How often to re-fetch logs for providers without a log stream
+const LOG_POLL_INTERVAL_SECS: u64 = 3;
+
+type ProviderFactory = fn(&CachedAccount) -> Result<Box<dyn DeploymentProvider>>;
+
+// Registered providers, keyed by `CachedAccount.provider`
+const PROVIDERS: &[(&str, ProviderFactory)] = &[
+    ("vercel", vercel::create_provider),
+    ("railway", railway::create_provider),
+    ("netlify", netlify::create_provider),
+    ("render", render::create_provider),
+    ("fly", fly::create_provider),
+    ("cloudflare", cloudflare::create_provider),
+];
+
+/// Create the provider client for an account
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    let (_, factory) = PROVIDERS
+        .iter()
+        .find(|(id, _)| *id == account.provider)
+        .ok_or_else(|| anyhow!("Unknown provider: {}", account.provider))?;
+
+    factory(account)
+}
+
+/// Milliseconds from `start` to `end`, when both are known and in order
+pub fn duration_ms(start: Option<i64>, end: Option<i64>) -> Option<i64> {
+    match (start, end) {
+        (Some(start), Some(end)) if end >= start => Some(end - start),
+        _ => None,
+    }
+}
+
+/// Parse an ISO 8601 timestamp into unix ms
+pub fn parse_timestamp(value: Option<&str>) -> Option<i64> {
+    value.and_then(|s| {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.timestamp_millis())
+    })
+}
+
+/// Combine the per-site/service/project listings of one account.
+///
+/// A failing listing is skipped while others succeed, so one broken site
+/// doesn't hide the rest of the account. Its error is passed up when every
+/// listing failed, or when it concerns the token itself (rate limited or
+/// revoked), so the account's fetch status reports it.
+pub fn merge_listings<T>(label: &str, results: Vec<Result<Vec<T>>>) -> Result<Vec<T>> {
+    let mut merged = Vec::new();
+    let mut succeeded = false;
+    let mut last_error = None;
+
+    for result in results {
+        match result {
+            Ok(items) => {
+                succeeded = true;
+                merged.extend(items);
+            }
+            Err(e) if is_token_error(&e) => return Err(e),
+            Err(e) => {
+                eprintln!("[{}] Skipping listing: {:#}", label, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    match last_error {
+        Some(e) if !succeeded => Err(e),
+        _ => Ok(merged),
+    }
+}
+
+fn is_token_error(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<AppError>(),
+            Some(AppError::Unauthorized { .. } | AppError::RateLimited { .. })
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_listings_skips_failures_while_others_succeed() {
+        let results = vec![Ok(vec![1, 2]), Err(anyhow!("site gone")), Ok(vec![3])];
+        assert_eq!(merge_listings("TEST", results).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn merge_listings_fails_when_every_listing_fails() {
+        let results: Vec<Result<Vec<i32>>> = vec![Err(anyhow!("first")), Err(anyhow!("second"))];
+        assert_eq!(merge_listings("TEST", results).unwrap_err().to_string(), "second");
+    }
+
+    #[test]
+    fn merge_listings_passes_token_errors_up() {
+        let limited = anyhow::Error::from(AppError::RateLimited { retry_after: Some(30), message: "slow down".into() });
+        let results = vec![Ok(vec![1]), Err(limited)];
+        let error = AppError::from(merge_listings("TEST", results).unwrap_err());
+        assert!(matches!(error, AppError::RateLimited { retry_after: Some(30), .. }));
+    }
+
+    #[test]
+    fn merge_listings_of_nothing_is_empty() {
+        let results: Vec<Result<Vec<i32>>> = Vec::new();
+        assert!(merge_listings("TEST", results).unwrap().is_empty());
+    }
+}
@@ -1,15 +1,21 @@
 use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use std::collections::HashMap;
+use std::future::Future;
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
+use crate::vercel::LogLine;
 use super::types::*;
 
 const API_BASE: &str = "https://backboard.railway.com/graphql/v2";
 
 pub struct RailwayClient {
     client: reqwest::Client,
-    #[allow(dead_code)]
+    endpoint: String,
     token: String,
-    #[allow(dead_code)]
     is_project_token: bool,
+    quota: Quota,
 }
 
 impl RailwayClient {
@@ -44,33 +50,73 @@ impl RailwayClient {
             .default_headers(headers)
             .build()?;
 
-        Ok(Self { client, token, is_project_token })
+        Ok(Self {
+            client,
+            endpoint: API_BASE.to_string(),
+            token,
+            is_project_token,
+            quota: Quota::default(),
+        })
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    // Send requests to a stub GraphQL server instead of Railway
+    #[cfg(test)]
+    fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn is_project_token(&self) -> bool {
+        self.is_project_token
     }
 
     async fn execute_query<T: serde::de::DeserializeOwned>(&self, query: &str, variables: Option<serde_json::Value>) -> Result<T> {
+        self.execute(query, variables, true).await
+    }
+
+    // Mutations aren't retried, so a timed out request can't run twice
+    async fn execute_mutation<T: serde::de::DeserializeOwned>(&self, query: &str, variables: Option<serde_json::Value>) -> Result<T> {
+        self.execute(query, variables, false).await
+    }
+
+    async fn execute<T: serde::de::DeserializeOwned>(&self, query: &str, variables: Option<serde_json::Value>, is_query: bool) -> Result<T> {
         let body = serde_json::json!({
             "query": query,
             "variables": variables.unwrap_or(serde_json::json!({}))
         });
 
-        let resp = self
-            .client
-            .post(API_BASE)
-            .json(&body)
-            .send()
-            .await?;
+        let req = self.client.post(&self.endpoint).json(&body);
+        let resp = if is_query {
+            req.send_query(&self.quota).await?
+        } else {
+            req.send_tracked(&self.quota).await?
+        };
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            return Err(anyhow!("GraphQL request failed: {} - {}", status, text));
+            return Err(AppError::from_response("GraphQL request failed", resp).await.into());
         }
 
         let response: GraphQLResponse<T> = resp.json().await?;
 
         if let Some(errors) = response.errors {
             if !errors.is_empty() {
-                return Err(anyhow!("GraphQL errors: {}", errors.iter().map(|e| e.message.clone()).collect::<Vec<_>>().join(", ")));
+                let code = errors.iter().find_map(|e| e.extensions.as_ref().and_then(|x| x.code.clone()));
+                let message = format!("GraphQL errors: {}", errors.iter().map(|e| e.message.clone()).collect::<Vec<_>>().join(", "));
+                let error = AppError::from_graphql(code, message);
+                if let AppError::RateLimited { retry_after, .. } = &error {
+                    self.quota.mark_limited(*retry_after);
+                }
+                return Err(error.into());
             }
         }
 
@@ -112,18 +158,20 @@ impl RailwayClient {
         Ok(data.teams.edges.into_iter().map(|e| e.node).collect())
     }
 
+    /// Projects with all their services and environments.
+    ///
+    /// `limit` caps the number of projects, None lists every project.
     pub async fn list_projects(&self, limit: Option<u32>) -> Result<Vec<RailwayProject>> {
-        let limit = limit.unwrap_or(50);
         let query = r#"
-            query Projects($first: Int!) {
-                projects(first: $first) {
+            query Projects($first: Int!, $after: String) {
+                projects(first: $first, after: $after) {
                     edges {
                         node {
                             id
                             name
                             description
                             updatedAt
-                            services {
+                            services(first: 100) {
                                 edges {
                                     node {
                                         id
@@ -131,27 +179,135 @@ impl RailwayClient {
                                         icon
                                     }
                                 }
+                                pageInfo {
+                                    hasNextPage
+                                    endCursor
+                                }
                             }
-                            environments {
+                            environments(first: 100) {
                                 edges {
                                     node {
                                         id
                                         name
                                     }
                                 }
+                                pageInfo {
+                                    hasNextPage
+                                    endCursor
+                                }
                             }
                         }
                     }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
                 }
             }
         "#;
 
-        let variables = serde_json::json!({
-            "first": limit
-        });
+        let mut projects = collect_pages(limit, None, |first, after| async move {
+            let variables = serde_json::json!({
+                "first": first,
+                "after": after
+            });
+
+            let data: ProjectsData = self.execute_query(query, Some(variables)).await?;
+            let nodes = data.projects.edges.into_iter().map(|e| e.node).collect();
+            Ok((nodes, data.projects.page_info))
+        })
+        .await?;
+
+        // Projects with more than one page of services or environments
+        for project in &mut projects {
+            if project.services.page_info.has_next_page {
+                let rest = self.list_project_services(&project.id, project.services.page_info.end_cursor.take()).await?;
+                project.services.edges.extend(rest.into_iter().map(|node| ServiceEdge { node }));
+                project.services.page_info.has_next_page = false;
+            }
+            if project.environments.page_info.has_next_page {
+                let rest = self.list_project_environments(&project.id, project.environments.page_info.end_cursor.take()).await?;
+                project.environments.edges.extend(rest.into_iter().map(|node| EnvironmentEdge { node }));
+                project.environments.page_info.has_next_page = false;
+            }
+        }
 
-        let data: ProjectsData = self.execute_query(query, Some(variables)).await?;
-        Ok(data.projects.edges.into_iter().map(|e| e.node).collect())
+        Ok(projects)
+    }
+
+    // Remaining services of a project, starting after `after`
+    async fn list_project_services(&self, project_id: &str, after: Option<String>) -> Result<Vec<RailwayService>> {
+        let query = r#"
+            query ProjectServices($id: String!, $first: Int!, $after: String) {
+                project(id: $id) {
+                    id
+                    name
+                    services(first: $first, after: $after) {
+                        edges {
+                            node {
+                                id
+                                name
+                                icon
+                            }
+                        }
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                    }
+                }
+            }
+        "#;
+
+        collect_pages(None, after, |first, after| async move {
+            let variables = serde_json::json!({
+                "id": project_id,
+                "first": first,
+                "after": after
+            });
+
+            let data: ProjectData = self.execute_query(query, Some(variables)).await?;
+            let services = data.project.services;
+            Ok((services.edges.into_iter().map(|e| e.node).collect(), services.page_info))
+        })
+        .await
+    }
+
+    // Remaining environments of a project, starting after `after`
+    async fn list_project_environments(&self, project_id: &str, after: Option<String>) -> Result<Vec<RailwayEnvironment>> {
+        let query = r#"
+            query ProjectEnvironments($id: String!, $first: Int!, $after: String) {
+                project(id: $id) {
+                    id
+                    name
+                    environments(first: $first, after: $after) {
+                        edges {
+                            node {
+                                id
+                                name
+                            }
+                        }
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                    }
+                }
+            }
+        "#;
+
+        collect_pages(None, after, |first, after| async move {
+            let variables = serde_json::json!({
+                "id": project_id,
+                "first": first,
+                "after": after
+            });
+
+            let data: ProjectData = self.execute_query(query, Some(variables)).await?;
+            let environments = data.project.environments;
+            Ok((environments.edges.into_iter().map(|e| e.node).collect(), environments.page_info))
+        })
+        .await
     }
 
     pub async fn list_deployments(
@@ -178,8 +334,8 @@ impl RailwayClient {
         limit: u32,
     ) -> Result<Vec<RailwayDeployment>> {
         let query = r#"
-            query Deployments($first: Int!, $input: DeploymentListInput!) {
-                deployments(first: $first, input: $input) {
+            query Deployments($first: Int!, $after: String, $input: DeploymentListInput!) {
+                deployments(first: $first, after: $after, input: $input) {
                     edges {
                         node {
                             id
@@ -188,8 +344,15 @@ impl RailwayClient {
                             createdAt
                             updatedAt
                             meta
+                            projectId
+                            serviceId
+                            environmentId
                         }
                     }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
                 }
             }
         "#;
@@ -205,17 +368,28 @@ impl RailwayClient {
             input.insert("environmentId".to_string(), serde_json::Value::String(eid.to_string()));
         }
 
-        let variables = serde_json::json!({
-            "first": limit,
-            "input": serde_json::Value::Object(input)
-        });
+        let input = serde_json::Value::Object(input);
+
+        let deployments = collect_pages(Some(limit), None, |first, after| {
+            let input = input.clone();
+            async move {
+                let variables = serde_json::json!({
+                    "first": first,
+                    "after": after,
+                    "input": input
+                });
+
+                let data: DeploymentsData = self.execute_query(query, Some(variables)).await?;
+                let nodes = data.deployments.edges.into_iter().map(|e| e.node).collect();
+                Ok((nodes, data.deployments.page_info))
+            }
+        })
+        .await?;
 
-        let data: DeploymentsData = self.execute_query(query, Some(variables)).await?;
-        let mut deployments: Vec<RailwayDeployment> = data.deployments.edges.into_iter().map(|e| {
-            let mut d = e.node;
-            d.project_id = project_id.map(|s| s.to_string());
-            d.service_id = service_id.map(|s| s.to_string());
-            d.environment_id = environment_id.map(|s| s.to_string());
+        let mut deployments: Vec<RailwayDeployment> = deployments.into_iter().map(|mut d| {
+            d.project_id = project_id.map(|s| s.to_string()).or(d.project_id);
+            d.service_id = service_id.map(|s| s.to_string()).or(d.service_id);
+            d.environment_id = environment_id.map(|s| s.to_string()).or(d.environment_id);
             d
         }).collect();
 
@@ -229,38 +403,104 @@ impl RailwayClient {
         Ok(deployments)
     }
 
+    /// Latest deployments across every project, service and environment.
+    ///
+    /// Deployments are fetched nested under their services, so this takes one
+    /// request per page of projects instead of one per service × environment.
     async fn list_all_deployments(&self, limit: u32) -> Result<Vec<RailwayDeployment>> {
-        // First get all projects with their services and environments
-        let projects = self.list_projects(Some(20)).await?;
+        let query = r#"
+            query DeploymentTree($first: Int!, $after: String, $perService: Int!) {
+                projects(first: $first, after: $after) {
+                    edges {
+                        node {
+                            id
+                            name
+                            services(first: 100) {
+                                edges {
+                                    node {
+                                        id
+                                        name
+                                        deployments(first: $perService) {
+                                            edges {
+                                                node {
+                                                    id
+                                                    staticUrl
+                                                    status
+                                                    createdAt
+                                                    updatedAt
+                                                    meta
+                                                    projectId
+                                                    serviceId
+                                                    environmentId
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                pageInfo {
+                                    hasNextPage
+                                    endCursor
+                                }
+                            }
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        "#;
+
+        // The newest `limit` overall are among each service's newest `limit`
+        let per_service = limit.min(PAGE_SIZE);
+        let mut requests = 0;
+
+        let projects = collect_pages(None, None, |_, after| {
+            requests += 1;
+            async move {
+                let variables = serde_json::json!({
+                    "first": TREE_PAGE_SIZE,
+                    "after": after,
+                    "perService": per_service
+                });
+
+                let data: DeploymentTreeData = self.execute_query(query, Some(variables)).await?;
+                let nodes = data.projects.edges.into_iter().map(|e| e.node).collect();
+                Ok((nodes, data.projects.page_info))
+            }
+        })
+        .await?;
 
         let mut all_deployments = Vec::new();
+        // Projects with more services than the nested query returned
+        let mut overflow: Vec<(String, String, Option<String>)> = Vec::new();
 
-        // For each project, get deployments for each service in ALL environments
-        for project in &projects {
-            let services: Vec<RailwayService> = project.services.edges.iter().map(|e| e.node.clone()).collect();
-            let environments: Vec<RailwayEnvironment> = project.environments.edges.iter().map(|e| e.node.clone()).collect();
-
-            // Check all environments, not just production
-            for env in &environments {
-                for service in &services {
-                    let deployments = self.list_deployments_filtered(
-                        Some(&project.id),
-                        Some(&service.id),
-                        Some(&env.id),
-                        5, // Limit per service per environment
-                    ).await;
-
-                    if let Ok(mut deps) = deployments {
-                        // Add service name and project name to each deployment
-                        for d in &mut deps {
-                            d.service_name = Some(format!("{} / {}", project.name, service.name));
-                        }
-                        all_deployments.extend(deps);
-                    }
+        for project in projects {
+            if project.services.page_info.has_next_page {
+                overflow.push((project.id.clone(), project.name.clone(), project.services.page_info.end_cursor.clone()));
+            }
+
+            for service in project.services.edges.into_iter().map(|e| e.node) {
+                let service_name = format!("{} / {}", project.name, service.name);
+                for edge in service.deployments.edges {
+                    let mut d = edge.node;
+                    d.project_id = Some(project.id.clone());
+                    d.service_id = Some(service.id.clone());
+                    d.service_name = Some(service_name.clone());
+                    all_deployments.push(d);
                 }
             }
         }
 
+        if !overflow.is_empty() {
+            let (extra, extra_requests) = self.list_overflow_deployments(overflow, per_service).await?;
+            all_deployments.extend(extra);
+            requests += extra_requests;
+        }
+
+        eprintln!("[RAILWAY] Listed {} deployments in {} requests", all_deployments.len(), requests);
+
         // Sort all deployments by created_at descending
         all_deployments.sort_by(|a, b| {
             let a_time = a.created_at.as_deref().unwrap_or("");
@@ -274,6 +514,49 @@ impl RailwayClient {
         Ok(all_deployments)
     }
 
+    // Deployments of the services after `cursor` in each project, a few
+    // requests at a time. Returns the deployments and the request count.
+    async fn list_overflow_deployments(
+        &self,
+        projects: Vec<(String, String, Option<String>)>,
+        per_service: u32,
+    ) -> Result<(Vec<RailwayDeployment>, usize)> {
+        let mut requests = projects.len();
+        let mut targets = Vec::new();
+
+        for (project_id, project_name, cursor) in projects {
+            let services = self.list_project_services(&project_id, cursor).await?;
+            for service in services {
+                targets.push((project_id.clone(), format!("{} / {}", project_name, service.name), service.id));
+            }
+        }
+        requests += targets.len();
+
+        let results: Vec<Result<Vec<RailwayDeployment>>> = stream::iter(targets)
+            .map(|(project_id, service_name, service_id)| async move {
+                let mut deployments = self
+                    .list_deployments_filtered(Some(&project_id), Some(&service_id), None, per_service)
+                    .await?;
+                for d in &mut deployments {
+                    d.service_name = Some(service_name.clone());
+                }
+                Ok(deployments)
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut deployments = Vec::new();
+        for result in results {
+            match result {
+                Ok(deps) => deployments.extend(deps),
+                Err(e) => eprintln!("[RAILWAY] Skipping service: {}", e),
+            }
+        }
+
+        Ok((deployments, requests))
+    }
+
     pub async fn get_deployment(&self, deployment_id: &str) -> Result<RailwayDeployment> {
         let query = r#"
             query Deployment($id: String!) {
@@ -284,6 +567,9 @@ impl RailwayClient {
                     createdAt
                     updatedAt
                     meta
+                    projectId
+                    serviceId
+                    environmentId
                 }
             }
         "#;
@@ -295,6 +581,237 @@ impl RailwayClient {
         let data: DeploymentData = self.execute_query(query, Some(variables)).await?;
         Ok(data.deployment)
     }
+
+    /// Build and deploy a deployment's source again, returning the new deployment
+    pub async fn redeploy_deployment(&self, deployment_id: &str) -> Result<RailwayDeployment> {
+        let query = r#"
+            mutation DeploymentRedeploy($id: String!) {
+                deploymentRedeploy(id: $id) {
+                    id
+                    staticUrl
+                    status
+                    createdAt
+                    updatedAt
+                    meta
+                    projectId
+                    serviceId
+                    environmentId
+                }
+            }
+        "#;
+
+        let source = self.check_mutation_scope(deployment_id).await?;
+        let variables = serde_json::json!({
+            "id": deployment_id
+        });
+
+        let data: RedeployData = self
+            .execute_mutation(query, Some(variables))
+            .await
+            .map_err(|e| scope_error(e, "redeploy"))?;
+
+        let mut deployment = data.deployment_redeploy;
+        deployment.project_id = deployment.project_id.or(source.project_id);
+        deployment.service_id = deployment.service_id.or(source.service_id);
+        deployment.environment_id = deployment.environment_id.or(source.environment_id);
+        Ok(deployment)
+    }
+
+    /// Restart a deployment's running container without rebuilding
+    pub async fn restart_deployment(&self, deployment_id: &str) -> Result<RailwayDeployment> {
+        self.check_mutation_scope(deployment_id).await?;
+        self.deployment_mutation("deploymentRestart", deployment_id, "restart").await?;
+        self.get_deployment(deployment_id).await
+    }
+
+    /// Deploy the image of an earlier deployment again, returning the
+    /// service's newest deployment
+    pub async fn rollback_deployment(&self, deployment_id: &str) -> Result<RailwayDeployment> {
+        let source = self.check_mutation_scope(deployment_id).await?;
+        self.deployment_mutation("deploymentRollback", deployment_id, "roll back").await?;
+
+        let latest = self
+            .list_deployments_filtered(
+                source.project_id.as_deref(),
+                source.service_id.as_deref(),
+                source.environment_id.as_deref(),
+                1,
+            )
+            .await?;
+        latest
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No deployment after rolling back to {}", deployment_id))
+    }
+
+    /// Cancel a queued or building deployment
+    pub async fn cancel_deployment(&self, deployment_id: &str) -> Result<RailwayDeployment> {
+        self.check_mutation_scope(deployment_id).await?;
+        self.deployment_mutation("deploymentCancel", deployment_id, "cancel").await?;
+        self.get_deployment(deployment_id).await
+    }
+
+    /// Remove a deployment, stopping it if it's running
+    pub async fn remove_deployment(&self, deployment_id: &str) -> Result<()> {
+        self.check_mutation_scope(deployment_id).await?;
+        self.deployment_mutation("deploymentRemove", deployment_id, "remove").await
+    }
+
+    // Run a `mutation($id) -> Boolean!` on a deployment
+    async fn deployment_mutation(&self, mutation: &str, deployment_id: &str, action: &str) -> Result<()> {
+        let query = format!("mutation($id: String!) {{ {}(id: $id) }}", mutation);
+        let variables = serde_json::json!({
+            "id": deployment_id
+        });
+
+        let data: HashMap<String, bool> = self
+            .execute_mutation(&query, Some(variables))
+            .await
+            .map_err(|e| scope_error(e, action))?;
+
+        match data.get(mutation) {
+            Some(true) => Ok(()),
+            _ => Err(AppError::provider(format!("Railway refused to {} deployment {}", action, deployment_id)).into()),
+        }
+    }
+
+    // Fetch the deployment, which also checks the token can see it, and make
+    // sure a project token is scoped to its project and environment
+    async fn check_mutation_scope(&self, deployment_id: &str) -> Result<RailwayDeployment> {
+        let deployment = self.get_deployment(deployment_id).await?;
+        if !self.is_project_token {
+            return Ok(deployment);
+        }
+
+        let query = r#"
+            query {
+                projectToken {
+                    projectId
+                    environmentId
+                }
+            }
+        "#;
+
+        let data: ProjectTokenData = self.execute_query(query, None).await?;
+        let scope = data.project_token;
+        let in_scope = deployment.project_id.as_deref().is_none_or(|id| id == scope.project_id)
+            && deployment.environment_id.as_deref().is_none_or(|id| id == scope.environment_id);
+
+        if !in_scope {
+            return Err(AppError::Forbidden {
+                message: format!("Project token is not scoped to the environment of deployment {}", deployment_id),
+            }
+            .into());
+        }
+        Ok(deployment)
+    }
+
+    pub async fn get_build_logs(&self, deployment_id: &str, limit: u32) -> Result<Vec<RailwayLog>> {
+        let query = r#"
+            query BuildLogs($deploymentId: String!, $limit: Int) {
+                buildLogs(deploymentId: $deploymentId, limit: $limit) {
+                    message
+                    timestamp
+                    severity
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "deploymentId": deployment_id,
+            "limit": limit
+        });
+
+        let data: BuildLogsData = self.execute_query(query, Some(variables)).await?;
+        Ok(data.build_logs)
+    }
+
+    pub async fn get_deployment_logs(&self, deployment_id: &str, limit: u32) -> Result<Vec<RailwayLog>> {
+        let query = r#"
+            query DeploymentLogs($deploymentId: String!, $limit: Int) {
+                deploymentLogs(deploymentId: $deploymentId, limit: $limit) {
+                    message
+                    timestamp
+                    severity
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "deploymentId": deployment_id,
+            "limit": limit
+        });
+
+        let data: DeploymentLogsData = self.execute_query(query, Some(variables)).await?;
+        Ok(data.deployment_logs)
+    }
+
+    /// Build logs followed by deploy logs
+    pub async fn get_all_logs(&self, deployment_id: &str, limit: u32) -> Result<Vec<LogLine>> {
+        let (build_logs, deployment_logs) = futures::try_join!(
+            self.get_build_logs(deployment_id, limit),
+            self.get_deployment_logs(deployment_id, limit)
+        )?;
+
+        Ok(build_logs
+            .iter()
+            .chain(deployment_logs.iter())
+            .map(|l| l.to_log_line())
+            .collect())
+    }
+}
+
+// Largest `first` Railway accepts per request
+const PAGE_SIZE: u32 = 100;
+// Projects per nested deployment query, kept small to stay under the query complexity limit
+const TREE_PAGE_SIZE: u32 = 20;
+// Parallel requests when falling back to per-service queries
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+// Follow `pageInfo.endCursor` from `after` until `limit` items are collected or the last page.
+// `fetch` gets the page size and the cursor to continue after.
+async fn collect_pages<T, F, Fut>(limit: Option<u32>, mut after: Option<String>, mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(u32, Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, PageInfo)>>,
+{
+    let mut items = Vec::new();
+
+    loop {
+        let first = match limit {
+            Some(limit) => limit.saturating_sub(items.len() as u32).min(PAGE_SIZE),
+            None => PAGE_SIZE,
+        };
+        if first == 0 {
+            break;
+        }
+
+        let (page, page_info) = fetch(first, after.take()).await?;
+        let received = page.len();
+        items.extend(page);
+
+        if !page_info.has_next_page || received == 0 {
+            break;
+        }
+        match page_info.end_cursor {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+
+    Ok(items)
+}
+
+// The token could read the deployment, so "not authorized" on a mutation
+// means its scope doesn't cover the action rather than an invalid token
+fn scope_error(e: anyhow::Error, action: &str) -> anyhow::Error {
+    match AppError::from(e) {
+        AppError::Unauthorized { message } | AppError::Forbidden { message } => AppError::Forbidden {
+            message: format!("Token is not allowed to {} this deployment: {}", action, message),
+        }
+        .into(),
+        other => other.into(),
+    }
 }
 
 pub fn create_client(token: &str) -> Result<RailwayClient> {
@@ -304,3 +821,191 @@ pub fn create_client(token: &str) -> Result<RailwayClient> {
 pub fn create_client_with_type(token: &str, token_type: &str) -> Result<RailwayClient> {
     RailwayClient::new(token.to_string(), Some(token_type))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    // Serve `respond(request)` as the `data` of every GraphQL request and
+    // record the request bodies. Returns the endpoint URL and the requests.
+    async fn stub_graphql<F>(respond: F) -> (String, Arc<Mutex<Vec<Value>>>)
+    where
+        F: Fn(&Value) -> Value + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let respond = Arc::new(respond);
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                let respond = respond.clone();
+                tokio::spawn(async move {
+                    let request: Value = serde_json::from_slice(&read_body(&mut stream).await).unwrap();
+                    let body = serde_json::to_vec(&json!({ "data": respond(&request) })).unwrap();
+                    recorded.lock().unwrap().push(request);
+
+                    let head = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    );
+                    stream.write_all(head.as_bytes()).await.unwrap();
+                    stream.write_all(&body).await.unwrap();
+                });
+            }
+        });
+
+        (endpoint, requests)
+    }
+
+    async fn read_body(stream: &mut TcpStream) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection closed mid-request");
+            buf.extend_from_slice(&chunk[..n]);
+
+            let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else { continue };
+            let head = String::from_utf8_lossy(&buf[..end]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |v| v.trim().parse().unwrap());
+            while buf.len() < end + 4 + length {
+                let n = stream.read(&mut chunk).await.unwrap();
+                assert!(n > 0, "connection closed mid-body");
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            return buf[end + 4..end + 4 + length].to_vec();
+        }
+    }
+
+    fn operation(request: &Value) -> &str {
+        let query = request["query"].as_str().unwrap().trim_start();
+        let name = query.strip_prefix("query ").unwrap_or(query);
+        &name[..name.find(|c: char| !c.is_alphanumeric()).unwrap_or(name.len())]
+    }
+
+    // Cursors are the index of the next item
+    fn page_start(request: &Value) -> usize {
+        request["variables"]["after"].as_str().map_or(0, |c| c.parse().unwrap())
+    }
+
+    fn deployment(n: usize) -> Value {
+        json!({
+            "id": format!("dep-{}", n),
+            "status": "SUCCESS",
+            "createdAt": format!("2024-01-01T00:{:02}:{:02}Z", n / 60, n % 60),
+        })
+    }
+
+    fn service(n: usize) -> Value {
+        json!({
+            "id": format!("svc-{}", n),
+            "name": format!("service {}", n),
+            "deployments": { "edges": [{ "node": deployment(n) }] },
+        })
+    }
+
+    fn client(endpoint: String) -> RailwayClient {
+        create_client("test-token").unwrap().with_endpoint(endpoint)
+    }
+
+    #[tokio::test]
+    async fn list_all_deployments_takes_one_request_per_page_of_projects() {
+        const PROJECTS: usize = 45;
+
+        let (endpoint, requests) = stub_graphql(|request| {
+            assert_eq!(operation(request), "DeploymentTree");
+            let start = page_start(request);
+            let end = (start + request["variables"]["first"].as_u64().unwrap() as usize).min(PROJECTS);
+            let projects: Vec<Value> = (start..end)
+                .map(|n| {
+                    json!({ "node": {
+                        "id": format!("proj-{}", n),
+                        "name": format!("project {}", n),
+                        "services": { "edges": [{ "node": service(n) }] },
+                    }})
+                })
+                .collect();
+            json!({ "projects": {
+                "edges": projects,
+                "pageInfo": { "hasNextPage": end < PROJECTS, "endCursor": end.to_string() },
+            }})
+        })
+        .await;
+
+        let deployments = client(endpoint).list_all_deployments(100).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), PROJECTS.div_ceil(TREE_PAGE_SIZE as usize));
+        assert!(requests.iter().all(|r| r["variables"]["first"] == TREE_PAGE_SIZE));
+
+        assert_eq!(deployments.len(), PROJECTS);
+        assert_eq!(deployments[0].id, "dep-44");
+        assert_eq!(deployments[0].project_id.as_deref(), Some("proj-44"));
+        assert_eq!(deployments[0].service_name.as_deref(), Some("project 44 / service 44"));
+    }
+
+    #[tokio::test]
+    async fn list_all_deployments_queries_services_past_the_nested_page_one_by_one() {
+        const NESTED: usize = 100;
+        const SERVICES: usize = 105;
+
+        let (endpoint, requests) = stub_graphql(|request| match operation(request) {
+            "DeploymentTree" => json!({ "projects": {
+                "edges": [{ "node": {
+                    "id": "big",
+                    "name": "big",
+                    "services": {
+                        "edges": (0..NESTED).map(|n| json!({ "node": service(n) })).collect::<Vec<_>>(),
+                        "pageInfo": { "hasNextPage": true, "endCursor": NESTED.to_string() },
+                    },
+                }}],
+                "pageInfo": { "hasNextPage": false, "endCursor": null },
+            }}),
+            "ProjectServices" => {
+                assert_eq!(request["variables"]["id"], "big");
+                let services: Vec<Value> = (page_start(request)..SERVICES)
+                    .map(|n| json!({ "node": { "id": format!("svc-{}", n), "name": format!("service {}", n) } }))
+                    .collect();
+                json!({ "project": {
+                    "id": "big",
+                    "name": "big",
+                    "services": { "edges": services, "pageInfo": { "hasNextPage": false } },
+                }})
+            }
+            "Deployments" => {
+                let service_id = request["variables"]["input"]["serviceId"].as_str().unwrap();
+                let n: usize = service_id.strip_prefix("svc-").unwrap().parse().unwrap();
+                json!({ "deployments": {
+                    "edges": [{ "node": deployment(n) }],
+                    "pageInfo": { "hasNextPage": false },
+                }})
+            }
+            other => panic!("unexpected query {}", other),
+        })
+        .await;
+
+        let deployments = client(endpoint).list_all_deployments(200).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let count = |name: &str| requests.iter().filter(|r| operation(r) == name).count();
+        assert_eq!(count("DeploymentTree"), 1);
+        assert_eq!(count("ProjectServices"), 1);
+        assert_eq!(count("Deployments"), SERVICES - NESTED);
+
+        assert_eq!(deployments.len(), SERVICES);
+        let overflow = deployments.iter().find(|d| d.id == "dep-104").unwrap();
+        assert_eq!(overflow.project_id.as_deref(), Some("big"));
+        assert_eq!(overflow.service_id.as_deref(), Some("svc-104"));
+        assert_eq!(overflow.service_name.as_deref(), Some("big / service 104"));
+    }
+}
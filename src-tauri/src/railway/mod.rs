@@ -1,5 +1,9 @@
 pub mod client;
+pub mod provider;
+pub mod subscription;
 pub mod types;
 
 pub use client::*;
+pub use provider::*;
+pub use subscription::*;
 pub use types::*;
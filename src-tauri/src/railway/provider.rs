@@ -0,0 +1,127 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crate::provider::{self, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use crate::vercel::LogLine;
+use super::client::{create_client_with_type, RailwayClient};
+use super::subscription::LogKind;
+use super::types::*;
+
+// Most recent lines fetched per log type
+const LOG_LIMIT: u32 = 500;
+// How often to check whether a streamed deployment has finished
+const STATUS_POLL_INTERVAL_SECS: u64 = 5;
+
+impl From<RailwayDeployment> for UnifiedDeployment {
+    fn from(d: RailwayDeployment) -> Self {
+        UnifiedDeployment {
+            id: d.id.clone(),
+            provider: "railway".to_string(),
+            name: d.service_name.clone().unwrap_or_else(|| "Deployment".to_string()),
+            url: d.static_url.clone(),
+            status: format!("{:?}", d.status).to_uppercase(),
+            created_at: provider::parse_timestamp(d.created_at.as_deref()),
+            commit_message: d.meta.as_ref().and_then(|m| m.commit_message.clone()),
+            branch: d.meta.as_ref().and_then(|m| m.branch.clone()),
+            project_id: d.project_id.clone(),
+            service_id: d.service_id.clone(),
+            git_author_login: None,  // Railway doesn't expose git author
+            team_slug: None,  // Not used for Railway
+            account_id: None,
+            queue_ms: None,  // Railway doesn't say when the build started
+            // Last update of a finished deployment, so this includes queueing and deploying
+            build_ms: if d.status.is_finished() {
+                provider::duration_ms(
+                    provider::parse_timestamp(d.created_at.as_deref()),
+                    provider::parse_timestamp(d.updated_at.as_deref()),
+                )
+            } else {
+                None
+            },
+            environment: None,  // Only the environment ID is fetched
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+#[async_trait]
+impl DeploymentProvider for RailwayClient {
+    async fn validate_token(&self) -> Result<()> {
+        // Workspace and project tokens can't use the `me` query, so list a project instead
+        RailwayClient::list_projects(self, Some(1)).await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let projects = RailwayClient::list_projects(self, None).await?;
+        Ok(projects
+            .into_iter()
+            .map(|p| UnifiedProject {
+                updated_at: provider::parse_timestamp(p.updated_at.as_deref()),
+                id: p.id,
+                provider: "railway".to_string(),
+                name: p.name,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let deployments = RailwayClient::list_deployments(self, None, None, None, limit).await?;
+        Ok(deployments.into_iter().map(UnifiedDeployment::from).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let deployment = RailwayClient::get_deployment(self, deployment_id).await?;
+        Ok(deployment.into())
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        self.get_all_logs(deployment_id, LOG_LIMIT).await
+    }
+
+    async fn stream_logs(&self, deployment_id: &str, on_log: &mut (dyn FnMut(LogLine) + Send)) -> Result<()> {
+        let is_building = |d: RailwayDeployment| UnifiedDeployment::from(d).is_building();
+
+        // Finished deployments won't produce new lines, so skip the socket
+        if !is_building(RailwayClient::get_deployment(self, deployment_id).await?) {
+            for line in self.fetch_logs(deployment_id).await? {
+                on_log(line);
+            }
+            return Ok(());
+        }
+
+        // Stop following once the deployment is no longer building
+        let finished = async {
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(STATUS_POLL_INTERVAL_SECS)).await;
+                if let Ok(d) = RailwayClient::get_deployment(self, deployment_id).await {
+                    if !is_building(d) {
+                        break;
+                    }
+                }
+            }
+            // Give the last lines time to arrive
+            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        };
+
+        self.subscribe_logs(
+            deployment_id,
+            &[LogKind::Build, LogKind::Deployment],
+            finished,
+            |_, line| on_log(line),
+        )
+        .await
+    }
+}
+
+/// Client with the auth header matching the account's token type
+pub fn create_client_for_account(account: &CachedAccount) -> Result<RailwayClient> {
+    // Use the correct client type based on the account scope
+    let token_type = if account.scope_type == "project" { "project" } else { "workspace" };
+    Ok(create_client_with_type(&account.token, token_type)?.with_quota(account.quota.clone()))
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    Ok(Box::new(create_client_for_account(account)?))
+}
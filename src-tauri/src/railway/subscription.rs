@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Result};
+use futures::{SinkExt, StreamExt};
+use std::future::Future;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use crate::vercel::LogLine;
+use super::client::RailwayClient;
+use super::types::*;
+
+// Railway serves GraphQL subscriptions over the graphql-transport-ws protocol
+const WS_ENDPOINT: &str = "wss://backboard.railway.com/graphql/v2";
+const WS_PROTOCOL: &str = "graphql-transport-ws";
+
+// Lines sent back when a subscription starts
+const INITIAL_LOG_LIMIT: u32 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogKind {
+    Build,
+    Deployment,
+}
+
+impl LogKind {
+    // Doubles as the subscription ID on the socket
+    fn field(&self) -> &'static str {
+        match self {
+            LogKind::Build => "buildLogs",
+            LogKind::Deployment => "deploymentLogs",
+        }
+    }
+
+    fn query(&self) -> String {
+        format!(
+            r#"
+            subscription Logs($deploymentId: String!, $limit: Int) {{
+                {}(deploymentId: $deploymentId, limit: $limit) {{
+                    message
+                    timestamp
+                    severity
+                }}
+            }}
+        "#,
+            self.field()
+        )
+    }
+}
+
+impl RailwayClient {
+    /// Subscribe to build and/or deploy logs of a deployment.
+    ///
+    /// Calls `on_log` for every line until the server completes all
+    /// subscriptions, the socket closes or `until` resolves.
+    pub async fn subscribe_logs<F, U>(
+        &self,
+        deployment_id: &str,
+        kinds: &[LogKind],
+        until: U,
+        mut on_log: F,
+    ) -> Result<()>
+    where
+        F: FnMut(LogKind, LogLine),
+        U: Future<Output = ()>,
+    {
+        let mut request = WS_ENDPOINT.into_client_request()?;
+        let headers = request.headers_mut();
+        headers.insert("Sec-WebSocket-Protocol", HeaderValue::from_static(WS_PROTOCOL));
+
+        // Same auth headers as HTTP requests, also sent in the init payload
+        let init_payload = if self.is_project_token() {
+            headers.insert("Project-Access-Token", HeaderValue::from_str(self.token())?);
+            serde_json::json!({ "Project-Access-Token": self.token() })
+        } else {
+            let bearer = format!("Bearer {}", self.token());
+            headers.insert("Authorization", HeaderValue::from_str(&bearer)?);
+            serde_json::json!({ "Authorization": bearer })
+        };
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await?;
+
+        send_json(&mut socket, serde_json::json!({
+            "type": "connection_init",
+            "payload": init_payload
+        })).await?;
+
+        // Wait for the server to accept the connection before subscribing
+        loop {
+            let message = socket
+                .next()
+                .await
+                .ok_or_else(|| anyhow!("Connection closed before acknowledgement"))??;
+            if let Message::Text(text) = message {
+                let event: serde_json::Value = serde_json::from_str(&text)?;
+                match event.get("type").and_then(|t| t.as_str()) {
+                    Some("connection_ack") => break,
+                    Some("ping") => send_json(&mut socket, serde_json::json!({ "type": "pong" })).await?,
+                    _ => return Err(anyhow!("Unexpected message before acknowledgement: {}", text)),
+                }
+            }
+        }
+
+        for kind in kinds {
+            send_json(&mut socket, serde_json::json!({
+                "id": kind.field(),
+                "type": "subscribe",
+                "payload": {
+                    "query": kind.query(),
+                    "variables": {
+                        "deploymentId": deployment_id,
+                        "limit": INITIAL_LOG_LIMIT
+                    }
+                }
+            })).await?;
+        }
+
+        let mut active = kinds.len();
+        tokio::pin!(until);
+
+        while active > 0 {
+            let message = tokio::select! {
+                _ = &mut until => break,
+                message = socket.next() => match message {
+                    Some(message) => message?,
+                    None => break,
+                },
+            };
+
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+
+            let event: serde_json::Value = serde_json::from_str(&text)?;
+            let kind = match event.get("id").and_then(|id| id.as_str()) {
+                Some("buildLogs") => Some(LogKind::Build),
+                Some("deploymentLogs") => Some(LogKind::Deployment),
+                _ => None,
+            };
+
+            match (event.get("type").and_then(|t| t.as_str()), kind) {
+                (Some("next"), Some(kind)) => {
+                    let logs = event
+                        .get("payload")
+                        .and_then(|p| p.get("data"))
+                        .and_then(|d| d.get(kind.field()))
+                        .cloned()
+                        .map(serde_json::from_value::<Vec<RailwayLog>>)
+                        .transpose()?
+                        .unwrap_or_default();
+
+                    for log in &logs {
+                        on_log(kind, log.to_log_line());
+                    }
+                }
+                (Some("error"), _) => {
+                    return Err(anyhow!("Subscription error: {}", event.get("payload").unwrap_or(&serde_json::Value::Null)));
+                }
+                (Some("complete"), Some(_)) => active -= 1,
+                (Some("ping"), _) => send_json(&mut socket, serde_json::json!({ "type": "pong" })).await?,
+                _ => {}
+            }
+        }
+
+        let _ = socket.close(None).await;
+        Ok(())
+    }
+}
+
+async fn send_json<S>(socket: &mut S, value: serde_json::Value) -> Result<()>
+where
+    S: futures::Sink<Message> + Unpin,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    socket.send(Message::Text(value.to_string().into())).await?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::vercel::LogLine;
 
 // GraphQL response wrapper types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +11,24 @@ pub struct GraphQLResponse<T> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphQLError {
     pub message: String,
+    #[serde(default)]
+    pub extensions: Option<GraphQLErrorExtensions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLErrorExtensions {
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+// Relay cursor info on every connection
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    #[serde(default)]
+    pub has_next_page: bool,
+    #[serde(default)]
+    pub end_cursor: Option<String>,
 }
 
 // User types
@@ -33,8 +52,11 @@ pub struct ProjectsData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProjectConnection {
     pub edges: Vec<ProjectEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,10 +79,19 @@ pub struct RailwayProject {
     pub environments: EnvironmentConnection,
 }
 
+// For single project queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectData {
+    pub project: RailwayProject,
+}
+
 // Service types
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct ServiceConnection {
     pub edges: Vec<ServiceEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,8 +110,11 @@ pub struct RailwayService {
 
 // Environment types
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct EnvironmentConnection {
     pub edges: Vec<EnvironmentEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,8 +137,11 @@ pub struct DeploymentsData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DeploymentConnection {
     pub edges: Vec<DeploymentEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,6 +168,19 @@ pub enum RailwayDeploymentStatus {
     Unknown,
 }
 
+impl RailwayDeploymentStatus {
+    /// Whether the build and deploy are over
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            RailwayDeploymentStatus::Success
+                | RailwayDeploymentStatus::Failed
+                | RailwayDeploymentStatus::Crashed
+                | RailwayDeploymentStatus::Sleeping
+        )
+    }
+}
+
 impl RailwayDeploymentStatus {
     /// Convert Railway status to unified status string for frontend
     pub fn to_unified(&self) -> &'static str {
@@ -166,13 +216,13 @@ pub struct RailwayDeployment {
     #[serde(default)]
     pub meta: Option<RailwayDeploymentMeta>,
     // Additional context for frontend
-    #[serde(skip_deserializing)]
+    #[serde(default)]
     pub project_id: Option<String>,
-    #[serde(skip_deserializing)]
+    #[serde(default)]
     pub service_id: Option<String>,
     #[serde(skip_deserializing)]
     pub service_name: Option<String>,
-    #[serde(skip_deserializing)]
+    #[serde(default)]
     pub environment_id: Option<String>,
 }
 
@@ -189,12 +239,116 @@ pub struct RailwayDeploymentMeta {
     pub commit_hash: Option<String>,
 }
 
+// Nested projects → services → deployments query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentTreeData {
+    pub projects: DeploymentTreeConnection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentTreeConnection {
+    pub edges: Vec<DeploymentTreeEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentTreeEdge {
+    pub node: ProjectDeployments,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDeployments {
+    pub id: String,
+    pub name: String,
+    pub services: ServiceDeploymentsConnection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceDeploymentsConnection {
+    pub edges: Vec<ServiceDeploymentsEdge>,
+    #[serde(default)]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceDeploymentsEdge {
+    pub node: ServiceDeployments,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceDeployments {
+    pub id: String,
+    pub name: String,
+    pub deployments: DeploymentConnection,
+}
+
 // For single deployment query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentData {
     pub deployment: RailwayDeployment,
 }
 
+// deploymentRedeploy mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedeployData {
+    pub deployment_redeploy: RailwayDeployment,
+}
+
+// Project and environment a project token is limited to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTokenData {
+    pub project_token: ProjectTokenScope,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTokenScope {
+    pub project_id: String,
+    pub environment_id: String,
+}
+
+// Log types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildLogsData {
+    pub build_logs: Vec<RailwayLog>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentLogsData {
+    pub deployment_logs: Vec<RailwayLog>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RailwayLog {
+    pub message: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+}
+
+impl RailwayLog {
+    pub fn to_log_line(&self) -> LogLine {
+        let lower = self.message.to_lowercase();
+        let is_error = self.severity.as_deref() == Some("error")
+            || lower.contains("error")
+            || lower.contains("failed");
+
+        LogLine {
+            timestamp: crate::provider::parse_timestamp(self.timestamp.as_deref()).unwrap_or(0),
+            text: self.message.clone(),
+            is_error,
+        }
+    }
+}
+
 // Workspace/Team types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspacesData {
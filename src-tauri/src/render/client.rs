@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION};
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
+use crate::provider;
+use super::types::*;
+
+const API_BASE: &str = "https://api.render.com/v1";
+// Services polled per account; each costs one deploys request per poll
+const MAX_SERVICES: u32 = 20;
+
+pub struct RenderClient {
+    client: reqwest::Client,
+    token: String,
+    quota: Quota,
+}
+
+impl RenderClient {
+    pub fn new(token: String) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token))?,
+        );
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self { client, token, quota: Quota::default() })
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// List the users and teams the API key can access
+    pub async fn list_owners(&self) -> Result<Vec<RenderOwner>> {
+        let resp = self
+            .client
+            .get(format!("{}/owners", API_BASE))
+            .query(&[("limit", "20")])
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list owners", resp).await.into());
+        }
+
+        let owners: Vec<OwnerWithCursor> = resp.json().await?;
+        Ok(owners.into_iter().map(|o| o.owner).collect())
+    }
+
+    pub async fn list_services(&self, limit: u32) -> Result<Vec<RenderService>> {
+        let resp = self
+            .client
+            .get(format!("{}/services", API_BASE))
+            .query(&[("limit", limit.to_string())])
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list services", resp).await.into());
+        }
+
+        let services: Vec<ServiceWithCursor> = resp.json().await?;
+        Ok(services.into_iter().map(|s| s.service).collect())
+    }
+
+    pub async fn list_deploys(&self, service: &RenderService, limit: u32) -> Result<Vec<RenderDeploy>> {
+        let resp = self
+            .client
+            .get(format!("{}/services/{}/deploys", API_BASE, service.id))
+            .query(&[("limit", limit.to_string())])
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to list deploys", resp).await.into());
+        }
+
+        let deploys: Vec<DeployWithCursor> = resp.json().await?;
+        Ok(deploys
+            .into_iter()
+            .map(|d| with_service(d.deploy, service))
+            .collect())
+    }
+
+    /// Latest deploys across the first `MAX_SERVICES` services
+    pub async fn list_all_deploys(&self, limit: u32) -> Result<Vec<RenderDeploy>> {
+        let services = self.list_services(MAX_SERVICES).await?;
+
+        // Fetch each service's deploys concurrently
+        let results = futures::future::join_all(
+            services.iter().map(|service| self.list_deploys(service, limit)),
+        )
+        .await;
+
+        let mut all_deploys = provider::merge_listings("RENDER", results)?;
+
+        // Sort by created_at descending (most recent first)
+        all_deploys.sort_by(|a, b| {
+            let a_time = a.created_at.as_deref().unwrap_or("");
+            let b_time = b.created_at.as_deref().unwrap_or("");
+            b_time.cmp(a_time)
+        });
+
+        all_deploys.truncate(limit as usize);
+
+        Ok(all_deploys)
+    }
+
+    pub async fn get_deploy(&self, service: &RenderService, deploy_id: &str) -> Result<RenderDeploy> {
+        let resp = self
+            .client
+            .get(format!("{}/services/{}/deploys/{}", API_BASE, service.id, deploy_id))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to get deploy", resp).await.into());
+        }
+
+        let deploy: RenderDeploy = resp.json().await?;
+        Ok(with_service(deploy, service))
+    }
+
+    /// Look up a deploy without knowing its service.
+    ///
+    /// Render only serves deploys under their service, so every service is
+    /// asked at once.
+    pub async fn find_deploy(&self, deploy_id: &str) -> Result<RenderDeploy> {
+        let services = self.list_services(100).await?;
+
+        let results = futures::future::join_all(
+            services.iter().map(|service| self.get_deploy(service, deploy_id)),
+        )
+        .await;
+
+        results
+            .into_iter()
+            .find_map(|r| r.ok())
+            .ok_or_else(|| anyhow!("Deploy {} not found", deploy_id))
+    }
+}
+
+// Add service context to a deploy
+fn with_service(mut deploy: RenderDeploy, service: &RenderService) -> RenderDeploy {
+    deploy.service_id = Some(service.id.clone());
+    deploy.service_name = Some(service.name.clone());
+    deploy.service_url = service.service_details.as_ref().and_then(|s| s.url.clone());
+    deploy.service_dashboard_url = service.dashboard_url.clone();
+    deploy.branch = service.branch.clone();
+    deploy
+}
+
+pub fn create_client(token: &str) -> Result<RenderClient> {
+    RenderClient::new(token.to_string())
+}
@@ -0,0 +1,74 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crate::provider::{self, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use crate::vercel::LogLine;
+use super::client::{create_client, RenderClient};
+use super::types::*;
+
+impl From<RenderDeploy> for UnifiedDeployment {
+    fn from(d: RenderDeploy) -> Self {
+        UnifiedDeployment {
+            id: d.id.clone(),
+            provider: "render".to_string(),
+            name: d.service_name.clone().unwrap_or_else(|| "Deploy".to_string()),
+            url: d.service_url.clone(),
+            status: d.status.to_unified().to_string(),
+            created_at: provider::parse_timestamp(d.created_at.as_deref()),
+            commit_message: d.commit.as_ref().and_then(|c| c.message.clone()),
+            branch: d.branch.clone(),
+            project_id: d.service_id.clone(),
+            service_id: d.service_id.clone(),
+            git_author_login: None,  // Render doesn't expose git author
+            team_slug: None,  // Not used for Render
+            account_id: None,
+            queue_ms: None,
+            build_ms: provider::duration_ms(
+                provider::parse_timestamp(d.created_at.as_deref()),
+                provider::parse_timestamp(d.finished_at.as_deref()),
+            ),
+            environment: None,
+            dashboard_url: d.service_dashboard_url.clone(),
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+#[async_trait]
+impl DeploymentProvider for RenderClient {
+    async fn validate_token(&self) -> Result<()> {
+        self.list_owners().await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let services = self.list_services(100).await?;
+        Ok(services
+            .into_iter()
+            .map(|s| UnifiedProject {
+                updated_at: provider::parse_timestamp(s.updated_at.as_deref()),
+                id: s.id,
+                provider: "render".to_string(),
+                name: s.name,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let deploys = self.list_all_deploys(limit).await?;
+        Ok(deploys.into_iter().map(UnifiedDeployment::from).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let deploy = self.find_deploy(deployment_id).await?;
+        Ok(deploy.into())
+    }
+
+    async fn fetch_logs(&self, _deployment_id: &str) -> Result<Vec<LogLine>> {
+        Err(anyhow!("Logs are not supported for Render deployments yet"))
+    }
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    Ok(Box::new(create_client(&account.token)?.with_quota(account.quota.clone())))
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+// List endpoints wrap every item together with its pagination cursor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerWithCursor {
+    pub owner: RenderOwner,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceWithCursor {
+    pub service: RenderService,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployWithCursor {
+    pub deploy: RenderDeploy,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// A user or team that owns services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderOwner {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(rename = "type", default)]
+    pub owner_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderService {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub service_type: Option<String>,
+    #[serde(default)]
+    pub repo: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub owner_id: Option<String>,
+    #[serde(default)]
+    pub dashboard_url: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub service_details: Option<RenderServiceDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderServiceDetails {
+    /// Public URL, only set for web services and static sites
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderDeployStatus {
+    Created,
+    Queued,
+    BuildInProgress,
+    UpdateInProgress,
+    PreDeployInProgress,
+    Live,
+    Deactivated,
+    BuildFailed,
+    UpdateFailed,
+    PreDeployFailed,
+    Canceled,
+    #[serde(other)]
+    Unknown,
+}
+
+impl RenderDeployStatus {
+    /// Convert Render status to unified status string for frontend
+    pub fn to_unified(&self) -> &'static str {
+        match self {
+            RenderDeployStatus::Created => "QUEUED",
+            RenderDeployStatus::Queued => "QUEUED",
+            RenderDeployStatus::BuildInProgress => "BUILDING",
+            RenderDeployStatus::UpdateInProgress => "BUILDING",
+            RenderDeployStatus::PreDeployInProgress => "BUILDING",
+            RenderDeployStatus::Live => "READY",
+            // Superseded by a newer deploy after going live
+            RenderDeployStatus::Deactivated => "READY",
+            RenderDeployStatus::BuildFailed => "ERROR",
+            RenderDeployStatus::UpdateFailed => "ERROR",
+            RenderDeployStatus::PreDeployFailed => "ERROR",
+            RenderDeployStatus::Canceled => "CANCELED",
+            RenderDeployStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderDeploy {
+    pub id: String,
+    pub status: RenderDeployStatus,
+    #[serde(default)]
+    pub commit: Option<RenderCommit>,
+    #[serde(default)]
+    pub trigger: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub finished_at: Option<String>,
+    // Additional context for frontend
+    #[serde(skip_deserializing)]
+    pub service_id: Option<String>,
+    #[serde(skip_deserializing)]
+    pub service_name: Option<String>,
+    #[serde(skip_deserializing)]
+    pub service_url: Option<String>,
+    #[serde(skip_deserializing)]
+    pub service_dashboard_url: Option<String>,
+    #[serde(skip_deserializing)]
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderCommit {
+    pub id: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
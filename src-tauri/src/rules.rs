@@ -0,0 +1,464 @@
+use std::sync::Mutex;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use crate::tracker::{DeploymentPhase, DeploymentStatusChange};
+
+/// What a matching rule does
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RuleAction {
+    Desktop,
+    // Desktop notification with the system sound
+    Sound,
+    // Post to integrations; all enabled ones when no IDs are given
+    #[serde(rename_all = "camelCase")]
+    Webhook {
+        #[serde(default)]
+        integration_ids: Vec<String>,
+    },
+    // Matching changes are dropped
+    None,
+}
+
+/// Matches status changes on their deployment and transition. Empty lists
+/// and missing fields match anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub providers: Vec<String>,
+    #[serde(default)]
+    pub account_ids: Vec<String>,
+    // Project IDs or names
+    #[serde(default)]
+    pub projects: Vec<String>,
+    // Glob where `*` matches any run of characters, e.g. "release/*"
+    pub branch_pattern: Option<String>,
+    pub environment: Option<String>,
+    pub from: Option<DeploymentPhase>,
+    pub to: Option<DeploymentPhase>,
+    pub actions: Vec<RuleAction>,
+}
+
+/// Local time window in which desktop notifications and sounds are held back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub enabled: bool,
+    // "HH:MM", local time; `end` before `start` spans midnight
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+        }
+    }
+}
+
+impl QuietHours {
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (start, end) = match (parse_time(&self.start), parse_time(&self.end)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return false,
+        };
+
+        if start <= end {
+            now >= start && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+}
+
+/// Rules and quiet hours, stored in the keychain with the accounts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettings {
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    #[serde(default)]
+    pub quiet_hours: QuietHours,
+}
+
+/// Integrations a change is posted to
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookTargets {
+    All,
+    Only(Vec<String>),
+}
+
+impl WebhookTargets {
+    pub fn includes(&self, integration_id: &str) -> bool {
+        match self {
+            WebhookTargets::All => true,
+            WebhookTargets::Only(ids) => ids.iter().any(|id| id == integration_id),
+        }
+    }
+}
+
+/// What to do with one status change
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub desktop: bool,
+    pub sound: bool,
+    pub webhooks: WebhookTargets,
+}
+
+#[derive(Default)]
+pub struct NotificationRules {
+    settings: Mutex<NotificationSettings>,
+}
+
+impl NotificationRules {
+    pub fn get(&self) -> NotificationSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, settings: NotificationSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    pub fn is_quiet(&self, now: NaiveTime) -> bool {
+        self.settings.lock().unwrap().quiet_hours.contains(now)
+    }
+
+    /// Decide how to report a change. The first enabled rule that matches
+    /// wins; without one, notable changes get a desktop notification and
+    /// every change goes to all integrations.
+    pub fn evaluate(&self, change: &DeploymentStatusChange, now: NaiveTime) -> Decision {
+        let settings = self.settings.lock().unwrap();
+
+        let mut decision = match settings.rules.iter().find(|r| r.enabled && r.matches(change)) {
+            Some(rule) => {
+                eprintln!("[RULES] {} matched rule {:?}", change.deployment.id, rule.name);
+                rule.decision()
+            }
+            None => Decision {
+                desktop: change.is_notable(),
+                sound: false,
+                webhooks: WebhookTargets::All,
+            },
+        };
+
+        if settings.quiet_hours.contains(now) {
+            decision.desktop = false;
+            decision.sound = false;
+        }
+        decision
+    }
+}
+
+impl NotificationRule {
+    fn matches(&self, change: &DeploymentStatusChange) -> bool {
+        let d = &change.deployment;
+
+        if !self.providers.is_empty() && !self.providers.contains(&d.provider) {
+            return false;
+        }
+        if !self.account_ids.is_empty() && !d.account_id.as_ref().is_some_and(|id| self.account_ids.contains(id)) {
+            return false;
+        }
+        if !self.projects.is_empty()
+            && !self
+                .projects
+                .iter()
+                .any(|p| d.project_id.as_ref() == Some(p) || d.name.eq_ignore_ascii_case(p))
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.branch_pattern {
+            if !d.branch.as_deref().is_some_and(|branch| glob_match(pattern, branch)) {
+                return false;
+            }
+        }
+        if let Some(environment) = &self.environment {
+            if !d.environment.as_ref().is_some_and(|env| env.eq_ignore_ascii_case(environment)) {
+                return false;
+            }
+        }
+        if self.from.is_some() && self.from != change.previous {
+            return false;
+        }
+        self.to.is_none_or(|to| to == change.current)
+    }
+
+    fn decision(&self) -> Decision {
+        let mut decision = Decision {
+            desktop: false,
+            sound: false,
+            webhooks: WebhookTargets::Only(Vec::new()),
+        };
+
+        for action in &self.actions {
+            match action {
+                RuleAction::Desktop => decision.desktop = true,
+                RuleAction::Sound => decision.sound = true,
+                RuleAction::Webhook { integration_ids } if integration_ids.is_empty() => {
+                    decision.webhooks = WebhookTargets::All;
+                }
+                RuleAction::Webhook { integration_ids } => {
+                    if let WebhookTargets::Only(ids) = &mut decision.webhooks {
+                        ids.extend(integration_ids.iter().cloned());
+                    }
+                }
+                RuleAction::None => {}
+            }
+        }
+        decision
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+// `*` matches any run of characters, everything else literally
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == value;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !value.starts_with(first) || value.len() < first.len() + last.len() || !value.ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &value[first.len()..value.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::UnifiedDeployment;
+
+    fn time(value: &str) -> NaiveTime {
+        parse_time(value).unwrap()
+    }
+
+    fn change(branch: &str, previous: Option<DeploymentPhase>, current: DeploymentPhase) -> DeploymentStatusChange {
+        DeploymentStatusChange {
+            deployment: UnifiedDeployment {
+                id: "dpl_1".to_string(),
+                branch: Some(branch.to_string()),
+                project_id: Some("prj_web".to_string()),
+                account_id: Some("acct".to_string()),
+                environment: Some("production".to_string()),
+                ..UnifiedDeployment::test_default()
+            },
+            previous,
+            current,
+        }
+    }
+
+    fn failed(branch: &str) -> DeploymentStatusChange {
+        change(branch, Some(DeploymentPhase::Building), DeploymentPhase::Error)
+    }
+
+    fn rule(name: &str, actions: Vec<RuleAction>) -> NotificationRule {
+        NotificationRule {
+            id: name.to_string(),
+            name: name.to_string(),
+            enabled: true,
+            providers: Vec::new(),
+            account_ids: Vec::new(),
+            projects: Vec::new(),
+            branch_pattern: None,
+            environment: None,
+            from: None,
+            to: None,
+            actions,
+        }
+    }
+
+    fn rules(rules: Vec<NotificationRule>, quiet_hours: QuietHours) -> NotificationRules {
+        let engine = NotificationRules::default();
+        engine.set(NotificationSettings { rules, quiet_hours });
+        engine
+    }
+
+    fn quiet(start: &str, end: &str) -> QuietHours {
+        QuietHours { enabled: true, start: start.to_string(), end: end.to_string() }
+    }
+
+    #[test]
+    fn glob_matches_prefix_patterns() {
+        assert!(glob_match("release/*", "release/1.2"));
+        assert!(glob_match("release/*", "release/"));
+        assert!(!glob_match("release/*", "release"));
+        assert!(!glob_match("release/*", "hotfix/release/1.2"));
+    }
+
+    #[test]
+    fn glob_star_matches_everything() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "main"));
+        assert!(glob_match("**", "feature/x"));
+    }
+
+    #[test]
+    fn glob_matches_several_stars_in_order() {
+        assert!(glob_match("a*b*c", "abc"));
+        assert!(glob_match("a*b*c", "a-x-b-y-c"));
+        assert!(glob_match("a*b*c", "abbbc"));
+        assert!(!glob_match("a*b*c", "acb"));
+        assert!(!glob_match("a*b*c", "ab"));
+        // The prefix and suffix can't share characters
+        assert!(!glob_match("a*a", "a"));
+    }
+
+    #[test]
+    fn glob_without_stars_is_exact() {
+        assert!(glob_match("main", "main"));
+        assert!(!glob_match("main", "main2"));
+        assert!(!glob_match("main", "Main"));
+    }
+
+    #[test]
+    fn quiet_hours_within_a_day() {
+        let hours = quiet("12:00", "14:00");
+        assert!(hours.contains(time("12:00")));
+        assert!(hours.contains(time("13:59")));
+        assert!(!hours.contains(time("14:00")));
+        assert!(!hours.contains(time("11:59")));
+    }
+
+    #[test]
+    fn quiet_hours_spanning_midnight() {
+        let hours = quiet("22:00", "08:00");
+        assert!(hours.contains(time("22:00")));
+        assert!(hours.contains(time("23:59")));
+        assert!(hours.contains(time("00:00")));
+        assert!(hours.contains(time("07:59")));
+        assert!(!hours.contains(time("08:00")));
+        assert!(!hours.contains(time("12:00")));
+        assert!(!hours.contains(time("21:59")));
+    }
+
+    #[test]
+    fn quiet_hours_off_when_disabled_or_invalid() {
+        let disabled = QuietHours { enabled: false, ..quiet("00:00", "23:59") };
+        assert!(!disabled.contains(time("12:00")));
+        assert!(!quiet("late", "08:00").contains(time("23:00")));
+        // An empty window never applies
+        assert!(!quiet("09:00", "09:00").contains(time("09:00")));
+    }
+
+    #[test]
+    fn rule_filters_must_all_match() {
+        let mut r = rule("release failures", vec![RuleAction::Desktop]);
+        r.providers = vec!["vercel".to_string()];
+        r.account_ids = vec!["acct".to_string()];
+        r.projects = vec!["WEB".to_string()];
+        r.branch_pattern = Some("release/*".to_string());
+        r.environment = Some("Production".to_string());
+        r.from = Some(DeploymentPhase::Building);
+        r.to = Some(DeploymentPhase::Error);
+
+        assert!(r.matches(&failed("release/2.0")));
+        assert!(!r.matches(&failed("main")));
+        assert!(!r.matches(&change("release/2.0", Some(DeploymentPhase::Building), DeploymentPhase::Ready)));
+        assert!(!r.matches(&change("release/2.0", None, DeploymentPhase::Error)));
+
+        let mut other_provider = failed("release/2.0");
+        other_provider.deployment.provider = "netlify".to_string();
+        assert!(!r.matches(&other_provider));
+
+        let mut no_branch = failed("release/2.0");
+        no_branch.deployment.branch = None;
+        assert!(!r.matches(&no_branch));
+    }
+
+    #[test]
+    fn projects_match_by_id_or_name() {
+        let mut r = rule("web", vec![RuleAction::Desktop]);
+        r.projects = vec!["prj_web".to_string()];
+        assert!(r.matches(&failed("main")));
+
+        r.projects = vec!["Web".to_string()];
+        assert!(r.matches(&failed("main")));
+
+        r.projects = vec!["api".to_string()];
+        assert!(!r.matches(&failed("main")));
+    }
+
+    #[test]
+    fn first_enabled_matching_rule_wins() {
+        let mut disabled = rule("disabled", vec![RuleAction::Sound]);
+        disabled.enabled = false;
+        let mut other_branch = rule("other branch", vec![RuleAction::Sound]);
+        other_branch.branch_pattern = Some("release/*".to_string());
+        let first = rule("first", vec![RuleAction::None]);
+        let second = rule("second", vec![RuleAction::Desktop, RuleAction::Sound]);
+
+        let engine = rules(vec![disabled, other_branch, first, second], QuietHours::default());
+        let decision = engine.evaluate(&failed("main"), time("12:00"));
+
+        assert_eq!(
+            decision,
+            Decision { desktop: false, sound: false, webhooks: WebhookTargets::Only(Vec::new()) }
+        );
+    }
+
+    #[test]
+    fn without_a_match_notable_changes_notify() {
+        let engine = rules(Vec::new(), QuietHours::default());
+
+        let decision = engine.evaluate(&failed("main"), time("12:00"));
+        assert_eq!(decision, Decision { desktop: true, sound: false, webhooks: WebhookTargets::All });
+
+        let started = change("main", None, DeploymentPhase::Building);
+        let decision = engine.evaluate(&started, time("12:00"));
+        assert_eq!(decision, Decision { desktop: false, sound: false, webhooks: WebhookTargets::All });
+    }
+
+    #[test]
+    fn webhook_actions_combine_integrations() {
+        let r = rule(
+            "hooks",
+            vec![
+                RuleAction::Webhook { integration_ids: vec!["slack".to_string()] },
+                RuleAction::Webhook { integration_ids: vec!["discord".to_string()] },
+            ],
+        );
+        let decision = rules(vec![r], QuietHours::default()).evaluate(&failed("main"), time("12:00"));
+        assert_eq!(decision.webhooks, WebhookTargets::Only(vec!["slack".to_string(), "discord".to_string()]));
+        assert!(decision.webhooks.includes("discord"));
+        assert!(!decision.webhooks.includes("teams"));
+
+        let all = rule("all", vec![RuleAction::Webhook { integration_ids: Vec::new() }]);
+        let decision = rules(vec![all], QuietHours::default()).evaluate(&failed("main"), time("12:00"));
+        assert_eq!(decision.webhooks, WebhookTargets::All);
+    }
+
+    #[test]
+    fn quiet_hours_hold_back_desktop_and_sound_only() {
+        let loud = rule("loud", vec![RuleAction::Desktop, RuleAction::Sound, RuleAction::Webhook { integration_ids: Vec::new() }]);
+        let engine = rules(vec![loud], quiet("22:00", "08:00"));
+
+        let night = engine.evaluate(&failed("main"), time("03:00"));
+        assert_eq!(night, Decision { desktop: false, sound: false, webhooks: WebhookTargets::All });
+        assert!(engine.is_quiet(time("03:00")));
+
+        let day = engine.evaluate(&failed("main"), time("09:00"));
+        assert_eq!(day, Decision { desktop: true, sound: true, webhooks: WebhookTargets::All });
+    }
+}
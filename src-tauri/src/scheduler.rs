@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+use crate::provider;
+use crate::state::{AppState, CachedAccount};
+use crate::tracker;
+use crate::tray;
+
+// Poll intervals per account
+const IDLE_INTERVAL: Duration = Duration::from_secs(30);
+const BUILDING_INTERVAL: Duration = Duration::from_secs(5);
+// Upper bound for the error backoff
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+// Deployments fetched per poll
+const POLL_LIMIT: u32 = 5;
+
+/// Runs one polling task per account on Tauri's async runtime
+#[derive(Default)]
+pub struct PollScheduler {
+    app: Mutex<Option<AppHandle>>,
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+    // Cuts the current sleep short in every task
+    wake: Arc<Notify>,
+}
+
+impl PollScheduler {
+    /// Start polling every known account
+    pub fn start(&self, app: AppHandle, accounts: &[CachedAccount]) {
+        *self.app.lock().unwrap() = Some(app);
+        self.sync(accounts);
+    }
+
+    /// Start tasks for new accounts and stop tasks of removed ones
+    pub fn sync(&self, accounts: &[CachedAccount]) {
+        let app = match self.app.lock().unwrap().clone() {
+            Some(app) => app,
+            // Not started yet; start() will pick the accounts up
+            None => return,
+        };
+
+        let mut tasks = self.tasks.lock().unwrap();
+
+        let removed: Vec<String> = tasks
+            .keys()
+            .filter(|id| !accounts.iter().any(|a| &a.id == *id))
+            .cloned()
+            .collect();
+        for id in removed {
+            if let Some(task) = tasks.remove(&id) {
+                task.abort();
+            }
+            let state = app.state::<AppState>();
+            state.tracker.forget_account(&id);
+            state.tray_status.forget_account(&id);
+            eprintln!("[SCHEDULER] Stopped polling {}", id);
+        }
+
+        for account in accounts {
+            if tasks.contains_key(&account.id) {
+                continue;
+            }
+            let task = tauri::async_runtime::spawn(run_account(
+                app.clone(),
+                account.id.clone(),
+                self.wake.clone(),
+            ));
+            tasks.insert(account.id.clone(), task);
+            eprintln!("[SCHEDULER] Started polling {}", account.id);
+        }
+
+        drop(tasks);
+        tray::refresh(&app);
+    }
+
+    /// Stop all tasks
+    pub fn stop(&self) {
+        for (_, task) in self.tasks.lock().unwrap().drain() {
+            task.abort();
+        }
+        if let Some(app) = self.app.lock().unwrap().as_ref() {
+            app.state::<AppState>().tray_status.clear();
+        }
+    }
+
+    /// Poll every account right away, e.g. when the UI saw a new build
+    pub fn wake(&self) {
+        self.wake.notify_waiters();
+    }
+}
+
+async fn run_account(app: AppHandle, account_id: String, wake: Arc<Notify>) {
+    let mut failures: u32 = 0;
+
+    loop {
+        let state = app.state::<AppState>();
+        let account = match state.get_account(&account_id) {
+            Some(account) => account,
+            // Account was removed
+            None => break,
+        };
+
+        let delay = match poll_account(&app, &state, &account).await {
+            Ok(is_building) => {
+                failures = 0;
+                if is_building { BUILDING_INTERVAL } else { IDLE_INTERVAL }
+            }
+            Err(e) => {
+                failures += 1;
+                let delay = backoff(failures);
+                eprintln!("[SCHEDULER] Polling {} failed ({}), retrying in {:?}", account_id, e, delay);
+                delay
+            }
+        };
+
+        // Space out polls while the token is limited or its quota runs low
+        let delay = match account.quota.throttle_delay() {
+            Some(throttle) if throttle > delay => {
+                eprintln!("[SCHEDULER] Throttling {} for {:?}", account_id, throttle);
+                throttle
+            }
+            _ => delay,
+        };
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = wake.notified() => {}
+        }
+    }
+}
+
+// Fetch the latest deployments of an account and publish what changed
+async fn poll_account(app: &AppHandle, state: &AppState, account: &CachedAccount) -> anyhow::Result<bool> {
+    let provider = provider::create_provider(account)?;
+    let mut deployments = provider.list_deployments(POLL_LIMIT).await?;
+
+    // Removed while the request was in flight
+    if state.get_account(&account.id).is_none() {
+        return Ok(false);
+    }
+
+    for d in &mut deployments {
+        d.account_id = Some(account.id.clone());
+    }
+
+    state.history.record(&deployments);
+    let changes = state.tracker.observe(&account.id, &deployments);
+    tracker::publish_changes(app, &changes);
+
+    state.tray_status.set_deployments(&account.id, &deployments);
+    tray::refresh(app);
+
+    Ok(deployments.iter().any(|d| d.is_building()))
+}
+
+// Exponential backoff starting at the idle interval
+fn backoff(failures: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+    IDLE_INTERVAL.saturating_mul(factor).min(MAX_BACKOFF)
+}
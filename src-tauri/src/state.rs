@@ -1,6 +1,15 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
+use crate::commands::deployments::AccountFetchResult;
+use crate::history::DeploymentHistory;
+use crate::http::Quota;
+use crate::integrations::Integrations;
+use crate::rules::NotificationRules;
+use crate::scheduler::PollScheduler;
+use crate::tracker::DeploymentTracker;
+use crate::tray::TrayStatus;
+use crate::webhook::WebhookServer;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,9 +21,30 @@ pub struct CachedAccount {
     pub scope_type: String,
     pub team_name: Option<String>,
     pub team_slug: Option<String>,
+    // Vercel team the token is scoped to, sent as `teamId` on every request
+    #[serde(default)]
+    pub team_id: Option<String>,
     pub token: String,
     #[serde(default = "default_provider")]
     pub provider: String,
+    // Provider-side account the token is scoped to (Cloudflare account ID)
+    #[serde(default)]
+    pub provider_account_id: Option<String>,
+    // Extra Vercel teams polled with the same token
+    #[serde(default)]
+    pub included_teams: Vec<TeamScope>,
+    // Rate limits of the token, dropped with the account
+    #[serde(skip)]
+    pub quota: Quota,
+}
+
+/// A Vercel team the user chose to include for an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamScope {
+    pub id: String,
+    pub slug: String,
+    pub name: String,
 }
 
 fn default_provider() -> String {
@@ -31,6 +61,22 @@ pub struct AppState {
     pub active_account_id: Mutex<Option<String>>,
     // Whether we've loaded from keychain
     pub initialized: Mutex<bool>,
+    // Last known status of every deployment
+    pub tracker: DeploymentTracker,
+    // Every deployment seen, persisted across restarts
+    pub history: DeploymentHistory,
+    // Optional listener for deployment webhooks
+    pub webhook: WebhookServer,
+    // Slack and Discord webhooks posted to on status changes
+    pub integrations: Integrations,
+    // Which status changes notify, and how
+    pub rules: NotificationRules,
+    // Building and failed deployments shown in the tray
+    pub tray_status: TrayStatus,
+    // Background polling per account
+    pub scheduler: PollScheduler,
+    // Outcome of each account's latest fetch, served with cached deployments
+    pub fetch_results: Mutex<HashMap<String, AccountFetchResult>>,
 }
 
 impl AppState {
@@ -49,6 +95,20 @@ impl AppState {
     pub fn remove_token(&self, account_id: &str) {
         self.tokens.lock().unwrap().remove(account_id);
         self.accounts.lock().unwrap().remove(account_id);
+        self.fetch_results.lock().unwrap().remove(account_id);
+    }
+
+    /// Last known fetch outcome of each of the given accounts
+    pub fn get_fetch_results(&self, account_ids: &[String]) -> Vec<AccountFetchResult> {
+        let results = self.fetch_results.lock().unwrap();
+        account_ids.iter().filter_map(|id| results.get(id).cloned()).collect()
+    }
+
+    pub fn set_fetch_results(&self, fetched: &[AccountFetchResult]) {
+        let mut results = self.fetch_results.lock().unwrap();
+        for result in fetched {
+            results.insert(result.account_id.clone(), result.clone());
+        }
     }
 
     pub fn get_account(&self, account_id: &str) -> Option<CachedAccount> {
@@ -80,6 +140,12 @@ impl AppState {
         *self.initialized.lock().unwrap() = val;
     }
 
+    pub fn set_included_teams(&self, account_id: &str, teams: Vec<TeamScope>) {
+        if let Some(account) = self.accounts.lock().unwrap().get_mut(account_id) {
+            account.included_teams = teams;
+        }
+    }
+
     pub fn rename_account(&self, account_id: &str, new_name: &str) {
         if let Some(account) = self.accounts.lock().unwrap().get_mut(account_id) {
             account.name = Some(new_name.to_string());
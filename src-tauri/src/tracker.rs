@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use crate::provider::UnifiedDeployment;
+use crate::state::AppState;
+use crate::tray;
+
+// System sound played by rules with the sound action
+const NOTIFICATION_SOUND: &str = "default";
+// Deployments remembered per account; the least recently seen go first
+const MAX_TRACKED_PER_ACCOUNT: usize = 500;
+
+/// Coarse lifecycle phase of a deployment, normalized across providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeploymentPhase {
+    Queued,
+    Building,
+    Ready,
+    Error,
+    Canceled,
+    Unknown,
+}
+
+impl DeploymentPhase {
+    pub fn from_status(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "QUEUED" | "WAITING" => DeploymentPhase::Queued,
+            "BUILDING" | "DEPLOYING" | "INITIALIZING" => DeploymentPhase::Building,
+            "READY" | "SUCCESS" | "SLEEPING" => DeploymentPhase::Ready,
+            "ERROR" | "FAILED" | "CRASHED" => DeploymentPhase::Error,
+            "CANCELED" | "REMOVED" | "REMOVING" | "SKIPPED" => DeploymentPhase::Canceled,
+            _ => DeploymentPhase::Unknown,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self, DeploymentPhase::Queued | DeploymentPhase::Building)
+    }
+}
+
+/// Payload of the `deployment-status-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentStatusChange {
+    pub deployment: UnifiedDeployment,
+    // None for deployments seen for the first time
+    pub previous: Option<DeploymentPhase>,
+    pub current: DeploymentPhase,
+}
+
+impl DeploymentStatusChange {
+    /// Whether this change raises a desktop notification when no rule says otherwise
+    pub fn is_notable(&self) -> bool {
+        match (self.previous, self.current) {
+            (Some(prev), DeploymentPhase::Ready) => prev.is_active(),
+            (_, DeploymentPhase::Error) => true,
+            _ => false,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let name = &self.deployment.name;
+        match self.current {
+            DeploymentPhase::Queued => format!("{} Deployment Queued", name),
+            DeploymentPhase::Building => format!("{} Deployment Started", name),
+            DeploymentPhase::Ready => format!("{} Deployment Successful", name),
+            DeploymentPhase::Error => format!("{} Deployment Failed", name),
+            DeploymentPhase::Canceled => format!("{} Deployment Canceled", name),
+            DeploymentPhase::Unknown => format!("{} Deployment {}", name, self.deployment.status),
+        }
+    }
+
+    /// Branch and commit message
+    pub fn body(&self) -> String {
+        let d = &self.deployment;
+        let commit_title = d.commit_message.clone().unwrap_or_else(|| "No commit message".to_string());
+        match &d.branch {
+            Some(branch) => format!("{}: {}", branch, commit_title),
+            None => commit_title,
+        }
+    }
+}
+
+// Deployment ID -> (phase, when last seen in unix ms)
+type AccountPhases = HashMap<String, (DeploymentPhase, i64)>;
+
+/// Last known phase of every deployment, used to detect transitions
+#[derive(Default)]
+pub struct DeploymentTracker {
+    phases: Mutex<HashMap<String, AccountPhases>>,
+    // When each account was first observed (unix ms)
+    seeded_at: Mutex<HashMap<String, i64>>,
+}
+
+impl DeploymentTracker {
+    /// Record the latest deployments of an account and return what changed.
+    ///
+    /// The first observation of an account only seeds the state. After that,
+    /// unseen deployments are reported if they are still in progress or were
+    /// created since seeding, so paging back through history stays quiet.
+    pub fn observe(&self, account_id: &str, deployments: &[UnifiedDeployment]) -> Vec<DeploymentStatusChange> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let seeded_at = {
+            let mut seeded = self.seeded_at.lock().unwrap();
+            match seeded.get(account_id) {
+                Some(at) => Some(*at),
+                None => {
+                    seeded.insert(account_id.to_string(), now);
+                    None
+                }
+            }
+        };
+
+        let mut all_phases = self.phases.lock().unwrap();
+        let phases = all_phases.entry(account_id.to_string()).or_default();
+        let mut changes = Vec::new();
+
+        for d in deployments {
+            let current = DeploymentPhase::from_status(&d.status);
+            let previous = phases.insert(d.id.clone(), (current, now)).map(|(phase, _)| phase);
+
+            let seeded_at = match seeded_at {
+                Some(at) => at,
+                None => continue,
+            };
+
+            let changed = match previous {
+                Some(prev) => prev != current,
+                None => current.is_active() || d.created_at.unwrap_or(0) >= seeded_at,
+            };
+
+            if changed {
+                changes.push(DeploymentStatusChange {
+                    deployment: d.clone(),
+                    previous,
+                    current,
+                });
+            }
+        }
+
+        if phases.len() > MAX_TRACKED_PER_ACCOUNT {
+            // Deployments of one poll share a timestamp, so ties are broken
+            // by ID to keep exactly the cap
+            let mut by_last_seen: Vec<(i64, String)> =
+                phases.iter().map(|(id, (_, seen))| (*seen, id.clone())).collect();
+            by_last_seen.sort_unstable_by(|a, b| b.cmp(a));
+            for (_, id) in by_last_seen.into_iter().skip(MAX_TRACKED_PER_ACCOUNT) {
+                phases.remove(&id);
+            }
+        }
+
+        changes
+    }
+
+    /// Forget a removed account, so adding it again seeds from scratch
+    pub fn forget_account(&self, account_id: &str) {
+        self.seeded_at.lock().unwrap().remove(account_id);
+        self.phases.lock().unwrap().remove(account_id);
+    }
+}
+
+/// Emit `deployment-status-changed` events, then run the notification
+/// rules for desktop notifications and posts to integrations
+pub fn publish_changes<R: Runtime>(app: &AppHandle<R>, changes: &[DeploymentStatusChange]) {
+    use tauri_plugin_notification::NotificationExt;
+
+    let state = app.try_state::<AppState>();
+
+    for change in changes {
+        eprintln!(
+            "[TRACKER] {} {:?} -> {:?}",
+            change.deployment.id, change.previous, change.current
+        );
+        let _ = app.emit("deployment-status-changed", change);
+
+        let state = match &state {
+            Some(state) => state,
+            None => continue,
+        };
+        if change.current == DeploymentPhase::Error {
+            state.tray_status.record_failure(&change.deployment);
+        }
+
+        let decision = state.rules.evaluate(change, chrono::Local::now().time());
+
+        if decision.desktop || decision.sound {
+            let mut notification = app.notification().builder().title(change.title()).body(change.body());
+            if decision.sound {
+                notification = notification.sound(NOTIFICATION_SOUND);
+            }
+            let _ = notification.show();
+        }
+        state.integrations.publish(change, &decision.webhooks);
+    }
+
+    if changes.iter().any(|c| c.current == DeploymentPhase::Error) {
+        tray::refresh(app);
+    }
+}
@@ -1,20 +1,26 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem},
+    image::Image,
+    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Manager, Runtime, PhysicalPosition, PhysicalSize, AppHandle,
 };
 
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_opener::OpenerExt;
+use crate::provider::UnifiedDeployment;
+use crate::state::{AppState, CachedAccount};
+use crate::tracker::DeploymentPhase;
+
 #[cfg(target_os = "macos")]
 use tauri_nspanel::ManagerExt;
 
 pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
-    // Include the tray icon at compile time
-    let icon_bytes = include_bytes!("../icons/tray.png");
-    let icon = tauri::image::Image::from_bytes(icon_bytes)
-        .unwrap_or_else(|_| app.default_window_icon().unwrap().clone());
+    let icon = state_icon(TrayState::Idle);
 
-    // Create menu with Quit option
-    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    // Recent deployments are added by refresh() once accounts are polled
+    let quit_item = MenuItem::with_id(app, QUIT_ID, "Quit", true, None::<&str>)?;
     let menu = Menu::with_items(app, &[&quit_item])?;
 
     let _tray = TrayIconBuilder::with_id("main")
@@ -24,8 +30,11 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
         .menu(&menu)
         .menu_on_left_click(false)
         .on_menu_event(|app, event| {
-            if event.id.as_ref() == "quit" {
+            if event.id.as_ref() == QUIT_ID {
+                app.state::<AppState>().scheduler.stop();
                 app.exit(0);
+            } else if let Some((action, deployment_id)) = parse_action_id(event.id.as_ref()) {
+                run_menu_action(app, action, deployment_id);
             }
         })
         .on_tray_icon_event(|tray, event| {
@@ -84,6 +93,7 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
 
                             // Show panel and make it key window
                             panel.show();
+                            acknowledge_failures(app);
                         }
                     }
                 }
@@ -97,6 +107,7 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
                         } else {
                             let _ = window.show();
                             let _ = window.set_focus();
+                            acknowledge_failures(app);
                         }
                     }
                 }
@@ -107,18 +118,380 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
-/// Set tray icon to "deploying" state
-pub fn set_tray_building<R: Runtime>(app: &AppHandle<R>, _project_name: Option<&str>) {
-    if let Some(tray) = app.tray_by_id("main") {
-        let _ = tray.set_title(Some("Deploying..."));
+const QUIT_ID: &str = "quit";
+// Recent deployments listed in the tray menu, across all accounts
+const MENU_DEPLOYMENTS: usize = 10;
+
+// Badge colors drawn over the tray glyph
+const BUILDING_COLOR: [u8; 3] = [0xea, 0xb3, 0x08];
+const FAILED_COLOR: [u8; 3] = [0xef, 0x44, 0x44];
+const READY_COLOR: [u8; 3] = [0x22, 0xc5, 0x5e];
+// Badged icons can't be templates, so the glyph is drawn in a grey that
+// reads on light and dark menu bars
+const GLYPH_GREY: u8 = 0x80;
+
+/// Overall state shown by the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayState {
+    // Nothing polled yet, or the latest deployments aren't all ready
+    Idle,
+    Building,
+    // A deployment failed and the user hasn't opened the app since
+    Failed,
+    // The newest deployment of every polled project is ready
+    AllGreen,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TrayDisplay {
+    state: TrayState,
+    title: String,
+    tooltip: String,
+}
+
+/// What the tray shows, gathered from every account's latest poll
+#[derive(Default)]
+pub struct TrayStatus {
+    // Names of building deployments per account
+    building: Mutex<HashMap<String, Vec<String>>>,
+    // Whether the newest deployment of each project is ready, per account
+    healthy: Mutex<HashMap<String, bool>>,
+    // Failed deployments not acknowledged yet: ID and project name
+    failed: Mutex<Vec<(String, String)>>,
+    shown: Mutex<Option<TrayDisplay>>,
+    // Menu entries last built, to skip rebuilding an unchanged menu
+    menu_entries: Mutex<Option<Vec<String>>>,
+}
+
+impl TrayStatus {
+    /// Record an account's latest deployments, newest first
+    pub fn set_deployments(&self, account_id: &str, deployments: &[UnifiedDeployment]) {
+        let building = deployments.iter().filter(|d| d.is_building()).map(|d| d.name.clone()).collect();
+
+        let mut seen = HashSet::new();
+        let healthy = deployments
+            .iter()
+            .filter(|d| seen.insert(d.project_id.clone().unwrap_or_else(|| d.name.clone())))
+            .all(|d| DeploymentPhase::from_status(&d.status) == DeploymentPhase::Ready);
+
+        self.building.lock().unwrap().insert(account_id.to_string(), building);
+        self.healthy.lock().unwrap().insert(account_id.to_string(), healthy);
+    }
+
+    /// Keep showing a failure until it's acknowledged
+    pub fn record_failure(&self, deployment: &UnifiedDeployment) {
+        let mut failed = self.failed.lock().unwrap();
+        if !failed.iter().any(|(id, _)| id == &deployment.id) {
+            failed.push((deployment.id.clone(), deployment.name.clone()));
+        }
+    }
+
+    pub fn acknowledge_failures(&self) {
+        self.failed.lock().unwrap().clear();
+    }
+
+    pub fn is_building(&self) -> bool {
+        self.building.lock().unwrap().values().any(|b| !b.is_empty())
+    }
+
+    pub fn forget_account(&self, account_id: &str) {
+        self.building.lock().unwrap().remove(account_id);
+        self.healthy.lock().unwrap().remove(account_id);
+    }
+
+    pub fn clear(&self) {
+        self.building.lock().unwrap().clear();
+        self.healthy.lock().unwrap().clear();
+    }
+
+    fn display(&self) -> TrayDisplay {
+        let building: Vec<String> = self.building.lock().unwrap().values().flatten().cloned().collect();
+        let failed: Vec<String> = self.failed.lock().unwrap().iter().map(|(_, name)| name.clone()).collect();
+        let healthy = self.healthy.lock().unwrap();
+        let all_green = !healthy.is_empty() && healthy.values().all(|h| *h);
+
+        let state = if !failed.is_empty() {
+            TrayState::Failed
+        } else if !building.is_empty() {
+            TrayState::Building
+        } else if all_green {
+            TrayState::AllGreen
+        } else {
+            TrayState::Idle
+        };
+
+        let mut counts = Vec::new();
+        if !building.is_empty() {
+            counts.push(format!("{} building", building.len()));
+        }
+        if !failed.is_empty() {
+            counts.push(format!("{} failed", failed.len()));
+        }
+
+        let mut tooltip = Vec::new();
+        if !building.is_empty() {
+            tooltip.push(format!("Building: {}", unique(&building).join(", ")));
+        }
+        if !failed.is_empty() {
+            tooltip.push(format!("Failed: {}", unique(&failed).join(", ")));
+        }
+        if tooltip.is_empty() {
+            tooltip.push(if all_green { "All deployments ready" } else { "Deployments" }.to_string());
+        }
+
+        TrayDisplay {
+            state,
+            title: counts.join(" · "),
+            tooltip: tooltip.join("\n"),
+        }
+    }
+}
+
+/// Redraw the tray icon and menu from the current status and history,
+/// if anything changed
+pub fn refresh<R: Runtime>(app: &AppHandle<R>) {
+    refresh_icon(app);
+    refresh_menu(app);
+}
+
+fn refresh_icon<R: Runtime>(app: &AppHandle<R>) {
+    let tray = match app.tray_by_id("main") {
+        Some(tray) => tray,
+        None => return,
+    };
+    let status = &app.state::<AppState>().tray_status;
+    let display = status.display();
+
+    // Swap in the new display and release the lock before touching the
+    // tray: its setters wait on the main thread, which may be waiting on us
+    let previous = {
+        let mut shown = status.shown.lock().unwrap();
+        if shown.as_ref() == Some(&display) {
+            return;
+        }
+        shown.replace(display.clone())
+    };
+
+    if previous.as_ref().map(|s| s.state) != Some(display.state) {
+        let _ = tray.set_icon(Some(state_icon(display.state)));
+        let _ = tray.set_icon_as_template(display.state == TrayState::Idle);
+    }
+    // Use empty string to clear title - None might not work on macOS
+    let _ = tray.set_title(Some(&display.title));
+    let _ = tray.set_tooltip(Some(&display.tooltip));
+}
+
+/// Clear failures once the user has seen them
+pub fn acknowledge_failures<R: Runtime>(app: &AppHandle<R>) {
+    app.state::<AppState>().tray_status.acknowledge_failures();
+    refresh(app);
+}
+
+// Rebuild the menu with the newest deployments, grouped by account
+fn refresh_menu<R: Runtime>(app: &AppHandle<R>) {
+    let state = app.state::<AppState>();
+
+    let mut accounts = state.get_all_accounts();
+    accounts.sort_by_key(account_label);
+    let account_ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+    let deployments = state.history.latest(&account_ids, MENU_DEPLOYMENTS);
+
+    let groups: Vec<(String, Vec<&UnifiedDeployment>)> = accounts
+        .iter()
+        .map(|a| {
+            let deployments = deployments.iter().filter(|d| d.account_id.as_ref() == Some(&a.id)).collect::<Vec<_>>();
+            (account_label(a), deployments)
+        })
+        .filter(|(_, deployments)| !deployments.is_empty())
+        .collect();
+
+    // Everything a menu entry shows or links to
+    let entries: Vec<String> = groups
+        .iter()
+        .flat_map(|(label, deployments)| {
+            std::iter::once(label.clone()).chain(deployments.iter().map(|d| {
+                format!("{}|{}|{}|{:?}|{:?}|{:?}", d.id, d.status, deployment_label(d), d.url, d.logs_url, d.dashboard_url)
+            }))
+        })
+        .collect();
+
+    let tray = match app.tray_by_id("main") {
+        Some(tray) => tray,
+        None => return,
+    };
+
+    // Menu items are created on the main thread, so don't hold the lock
+    // while building them
+    {
+        let mut built = state.tray_status.menu_entries.lock().unwrap();
+        if built.as_ref() == Some(&entries) {
+            return;
+        }
+        *built = Some(entries);
+    }
+
+    match build_menu(app, &groups) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => {
+            eprintln!("[TRAY] Failed to build menu: {}", e);
+            // Try again on the next refresh
+            *state.tray_status.menu_entries.lock().unwrap() = None;
+        }
+    }
+}
+
+fn build_menu<R: Runtime>(app: &AppHandle<R>, groups: &[(String, Vec<&UnifiedDeployment>)]) -> tauri::Result<Menu<R>> {
+    let menu = Menu::new(app)?;
+
+    for (label, deployments) in groups {
+        menu.append(&MenuItem::new(app, label, false, None::<&str>)?)?;
+        for d in deployments {
+            let open = MenuItem::with_id(app, action_id("open", &d.id), "Open URL", d.url.is_some(), None::<&str>)?;
+            let logs = MenuItem::with_id(app, action_id("logs", &d.id), "Open Logs", d.logs_url.is_some(), None::<&str>)?;
+            let copy = MenuItem::with_id(app, action_id("copy", &d.id), "Copy URL", d.url.is_some(), None::<&str>)?;
+            let dashboard = MenuItem::with_id(
+                app,
+                action_id("dashboard", &d.id),
+                "Open Dashboard",
+                d.dashboard_url.is_some(),
+                None::<&str>,
+            )?;
+            let submenu = Submenu::with_items(app, deployment_label(d), true, &[&open, &logs, &copy, &dashboard])?;
+            menu.append(&submenu)?;
+        }
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    menu.append(&MenuItem::with_id(app, QUIT_ID, "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn run_menu_action<R: Runtime>(app: &AppHandle<R>, action: &str, deployment_id: &str) {
+    let deployment = match app.state::<AppState>().history.get(deployment_id) {
+        Some(d) => d,
+        None => return,
+    };
+
+    let result = match action {
+        "copy" => match deployment.url.as_deref() {
+            Some(url) => app.clipboard().write_text(web_url(url)).map_err(|e| e.to_string()),
+            None => Ok(()),
+        },
+        _ => {
+            let url = match action {
+                "open" => deployment.url.as_deref().map(web_url),
+                "logs" => deployment.logs_url,
+                "dashboard" => deployment.dashboard_url,
+                _ => None,
+            };
+            match url {
+                Some(url) => app.opener().open_url(url, None::<&str>).map_err(|e| e.to_string()),
+                None => Ok(()),
+            }
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("[TRAY] {} {} failed: {}", action, deployment_id, e);
+    }
+}
+
+// Menu IDs are "deployment:<action>:<id>"
+fn action_id(action: &str, deployment_id: &str) -> String {
+    format!("deployment:{}:{}", action, deployment_id)
+}
+
+fn parse_action_id(id: &str) -> Option<(&str, &str)> {
+    id.strip_prefix("deployment:")?.split_once(':')
+}
+
+fn account_label(account: &CachedAccount) -> String {
+    let name = account
+        .team_name
+        .clone()
+        .or_else(|| account.name.clone())
+        .unwrap_or_else(|| account.username.clone());
+    format!("{} ({})", name, provider_name(&account.provider))
+}
+
+fn provider_name(provider: &str) -> &str {
+    match provider {
+        "vercel" => "Vercel",
+        "railway" => "Railway",
+        "netlify" => "Netlify",
+        "render" => "Render",
+        "fly" => "Fly.io",
+        "cloudflare" => "Cloudflare",
+        other => other,
     }
 }
 
-/// Set tray icon to normal state
-pub fn set_tray_normal<R: Runtime>(app: &AppHandle<R>) {
-    if let Some(tray) = app.tray_by_id("main") {
-        // Use empty string to clear title - None might not work on macOS
-        let _ = tray.set_title(Some(""));
-        let _ = tray.set_tooltip(Some("Deployments"));
+fn deployment_label(d: &UnifiedDeployment) -> String {
+    let glyph = match DeploymentPhase::from_status(&d.status) {
+        DeploymentPhase::Queued | DeploymentPhase::Building => "🟡",
+        DeploymentPhase::Ready => "🟢",
+        DeploymentPhase::Error => "🔴",
+        DeploymentPhase::Canceled | DeploymentPhase::Unknown => "⚪",
+    };
+    match &d.branch {
+        Some(branch) => format!("{} {} · {}", glyph, d.name, branch),
+        None => format!("{} {}", glyph, d.name),
     }
 }
+
+// Some providers report bare hostnames
+fn web_url(url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+fn base_icon() -> Image<'static> {
+    Image::from_bytes(include_bytes!("../icons/tray.png")).expect("tray icon is a valid PNG")
+}
+
+// The tray glyph, with a colored dot in the bottom right corner unless idle
+fn state_icon(state: TrayState) -> Image<'static> {
+    let color = match state {
+        TrayState::Idle => return base_icon(),
+        TrayState::Building => BUILDING_COLOR,
+        TrayState::Failed => FAILED_COLOR,
+        TrayState::AllGreen => READY_COLOR,
+    };
+
+    let icon = base_icon();
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+
+    let radius = width.min(height) as f64 * 0.22;
+    let (cx, cy) = (width as f64 - radius - 0.5, height as f64 - radius - 0.5);
+    // Clear a gap around the dot so it stands apart from the glyph
+    let gap = radius * 1.35;
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = ((y * width + x) * 4) as usize;
+            let distance = ((x as f64 - cx).powi(2) + (y as f64 - cy).powi(2)).sqrt();
+
+            if distance <= radius {
+                rgba[i..i + 3].copy_from_slice(&color);
+                rgba[i + 3] = 0xff;
+            } else if distance <= gap {
+                rgba[i + 3] = 0;
+            } else {
+                rgba[i..i + 3].fill(GLYPH_GREY);
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
+
+fn unique(names: &[String]) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    names.iter().map(String::as_str).filter(|n| seen.insert(*n)).collect()
+}
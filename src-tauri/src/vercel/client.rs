@@ -1,12 +1,23 @@
 use anyhow::{anyhow, Result};
+use futures::stream::{self, Stream, TryStreamExt};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::Method;
+use crate::error::AppError;
+use crate::http::{Quota, TrackedSend};
 use super::types::*;
 
 const API_BASE: &str = "https://api.vercel.com";
+// Largest page the list endpoints accept
+const PAGE_LIMIT: u32 = 100;
 
 pub struct VercelClient {
     client: reqwest::Client,
     token: String,
+    // Team/user slug used for dashboard URLs
+    team_slug: Option<String>,
+    // Team that team-scoped resources are requested for
+    team_id: Option<String>,
+    quota: Quota,
 }
 
 impl VercelClient {
@@ -21,88 +32,339 @@ impl VercelClient {
             .default_headers(headers)
             .build()?;
 
-        Ok(Self { client, token })
+        Ok(Self { client, token, team_slug: None, team_id: None, quota: Quota::default() })
+    }
+
+    pub fn with_team_slug(mut self, team_slug: String) -> Self {
+        self.team_slug = Some(team_slug);
+        self
+    }
+
+    pub fn with_team_id(mut self, team_id: Option<String>) -> Self {
+        self.team_id = team_id;
+        self
+    }
+
+    /// Share the account's quota, so limits carry over between clients
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
     }
 
     pub fn token(&self) -> &str {
         &self.token
     }
 
+    pub fn team_slug(&self) -> Option<&str> {
+        self.team_slug.as_deref()
+    }
+
+    pub fn team_id(&self) -> Option<&str> {
+        self.team_id.as_deref()
+    }
+
+    // Request for a team-scoped resource
+    fn team_request(&self, method: Method, url: String) -> reqwest::RequestBuilder {
+        let req = self.client.request(method, url);
+        match &self.team_id {
+            Some(team_id) => req.query(&[("teamId", team_id)]),
+            None => req,
+        }
+    }
+
+    fn team_get(&self, url: String) -> reqwest::RequestBuilder {
+        self.team_request(Method::GET, url)
+    }
+
     pub async fn get_user(&self) -> Result<User> {
         let resp = self
             .client
             .get(format!("{}/v2/user", API_BASE))
-            .send()
+            .send_tracked(&self.quota)
             .await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to get user: {}", resp.status()));
+            return Err(AppError::from_response("Failed to get user", resp).await.into());
         }
 
         let user_resp: UserResponse = resp.json().await?;
         Ok(user_resp.user)
     }
 
-    pub async fn list_projects(&self) -> Result<Vec<Project>> {
-        let resp = self
-            .client
-            .get(format!("{}/v9/projects", API_BASE))
-            .query(&[("limit", "50")])
-            .send()
-            .await?;
+    pub async fn list_projects_page(&self, cursor: PageCursor, limit: u32) -> Result<Page<Project>> {
+        let mut req = self
+            .team_get(format!("{}/v9/projects", API_BASE))
+            .query(&[("limit", limit.to_string())]);
+
+        // Projects page on `from` rather than `until`
+        req = match cursor {
+            PageCursor::Latest => req,
+            PageCursor::Until(ts) => req.query(&[("from", ts.to_string())]),
+            PageCursor::Since(ts) => req.query(&[("since", ts.to_string())]),
+        };
+
+        let resp = req.send_tracked(&self.quota).await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to list projects: {}", resp.status()));
+            return Err(AppError::from_response("Failed to list projects", resp).await.into());
         }
 
         let projects_resp: ProjectsResponse = resp.json().await?;
-        Ok(projects_resp.projects)
+        Ok(Page {
+            items: projects_resp.projects,
+            next: projects_resp.pagination.and_then(|p| p.next),
+        })
     }
 
-    pub async fn list_deployments(&self, project_id: Option<&str>, limit: u32) -> Result<Vec<Deployment>> {
+    /// Pages of projects, following `pagination.next` until exhausted
+    pub fn project_pages(&self, limit: u32) -> impl Stream<Item = Result<Vec<Project>>> + '_ {
+        stream::try_unfold(Some(PageCursor::Latest), move |cursor| async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok(None),
+            };
+            let page = self.list_projects_page(cursor, limit).await?;
+            Ok(Some((page.items, page.next.map(PageCursor::Until))))
+        })
+    }
+
+    /// Every project, across all pages
+    pub async fn list_projects(&self) -> Result<Vec<Project>> {
+        self.project_pages(PAGE_LIMIT).try_concat().await
+    }
+
+    pub async fn list_deployments_page(
+        &self,
+        project_id: Option<&str>,
+        cursor: PageCursor,
+        limit: u32,
+    ) -> Result<Page<Deployment>> {
         let mut req = self
-            .client
-            .get(format!("{}/v6/deployments", API_BASE))
+            .team_get(format!("{}/v6/deployments", API_BASE))
             .query(&[("limit", limit.to_string())]);
 
         if let Some(pid) = project_id {
             req = req.query(&[("projectId", pid)]);
         }
 
-        let resp = req.send().await?;
+        req = match cursor {
+            PageCursor::Latest => req,
+            PageCursor::Until(ts) => req.query(&[("until", ts.to_string())]),
+            PageCursor::Since(ts) => req.query(&[("since", ts.to_string())]),
+        };
+
+        let resp = req.send_tracked(&self.quota).await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to list deployments: {}", resp.status()));
+            return Err(AppError::from_response("Failed to list deployments", resp).await.into());
         }
 
         let deployments_resp: DeploymentsResponse = resp.json().await?;
-        Ok(deployments_resp.deployments)
+        Ok(Page {
+            items: deployments_resp.deployments,
+            next: deployments_resp.pagination.and_then(|p| p.next),
+        })
+    }
+
+    /// Pages of deployments, newest first, following `pagination.next`
+    pub fn deployment_pages<'a>(
+        &'a self,
+        project_id: Option<&'a str>,
+        limit: u32,
+    ) -> impl Stream<Item = Result<Vec<Deployment>>> + 'a {
+        stream::try_unfold(Some(PageCursor::Latest), move |cursor| async move {
+            let cursor = match cursor {
+                Some(cursor) => cursor,
+                None => return Ok(None),
+            };
+            let page = self.list_deployments_page(project_id, cursor, limit).await?;
+            Ok(Some((page.items, page.next.map(PageCursor::Until))))
+        })
+    }
+
+    /// Latest `limit` deployments, paging past the API's per-request cap
+    pub async fn list_deployments(&self, project_id: Option<&str>, limit: u32) -> Result<Vec<Deployment>> {
+        let mut deployments = Vec::new();
+        let mut pages = std::pin::pin!(self.deployment_pages(project_id, limit.min(PAGE_LIMIT)));
+
+        while deployments.len() < limit as usize {
+            match pages.try_next().await? {
+                Some(page) => deployments.extend(page),
+                None => break,
+            }
+        }
+
+        deployments.truncate(limit as usize);
+        Ok(deployments)
+    }
+
+    /// Deployments created after `since` (unix ms), newest first
+    pub async fn list_deployments_since(&self, project_id: Option<&str>, since: i64, limit: u32) -> Result<Vec<Deployment>> {
+        let page = self.list_deployments_page(project_id, PageCursor::Since(since), limit).await?;
+        Ok(page.items)
     }
 
     pub async fn get_deployment(&self, deployment_id: &str) -> Result<Deployment> {
         let resp = self
-            .client
-            .get(format!("{}/v13/deployments/{}", API_BASE, deployment_id))
-            .send()
+            .team_get(format!("{}/v13/deployments/{}", API_BASE, deployment_id))
+            .send_tracked(&self.quota)
             .await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to get deployment: {}", resp.status()));
+            return Err(AppError::from_response("Failed to get deployment", resp).await.into());
         }
 
         let deployment: Deployment = resp.json().await?;
         Ok(deployment)
     }
 
+    /// Cancel a queued or building deployment
+    pub async fn cancel_deployment(&self, deployment_id: &str) -> Result<Deployment> {
+        let resp = self
+            .team_request(Method::PATCH, format!("{}/v12/deployments/{}/cancel", API_BASE, deployment_id))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to cancel deployment", resp).await.into());
+        }
+
+        self.get_deployment(deployment_id).await
+    }
+
+    /// Build a deployment's source again, returning the new deployment
+    pub async fn redeploy(&self, deployment_id: &str, use_build_cache: bool) -> Result<Deployment> {
+        let source = self.get_deployment(deployment_id).await?;
+
+        let mut body = serde_json::json!({
+            "name": source.name,
+            "deploymentId": deployment_id,
+            "meta": { "action": "redeploy" }
+        });
+        if let Some(target) = &source.target {
+            body["target"] = serde_json::json!(target);
+        }
+        if !use_build_cache {
+            body["build"] = serde_json::json!({ "env": { "VERCEL_FORCE_NO_BUILD_CACHE": "1" } });
+        }
+
+        let resp = self
+            .team_request(Method::POST, format!("{}/v13/deployments", API_BASE))
+            .query(&[("forceNew", "1")])
+            .json(&body)
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to redeploy", resp).await.into());
+        }
+
+        let created: serde_json::Value = resp.json().await?;
+        let id = created
+            .get("id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow!("No deployment ID in redeploy response"))?;
+        self.get_deployment(id).await
+    }
+
+    /// Promote a deployment to production, returning the promoted deployment
+    pub async fn promote_deployment(&self, deployment_id: &str) -> Result<Deployment> {
+        let project_id = self.deployment_project_id(deployment_id).await?;
+
+        let resp = self
+            .team_request(Method::POST, format!("{}/v10/projects/{}/promote/{}", API_BASE, project_id, deployment_id))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to promote deployment", resp).await.into());
+        }
+
+        self.get_deployment(deployment_id).await
+    }
+
+    /// Point production back at an earlier deployment (Instant Rollback)
+    pub async fn rollback_deployment(&self, deployment_id: &str) -> Result<Deployment> {
+        let project_id = self.deployment_project_id(deployment_id).await?;
+
+        let resp = self
+            .team_request(Method::POST, format!("{}/v9/projects/{}/rollback/{}", API_BASE, project_id, deployment_id))
+            .send_tracked(&self.quota)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to roll back", resp).await.into());
+        }
+
+        self.get_deployment(deployment_id).await
+    }
+
+    async fn deployment_project_id(&self, deployment_id: &str) -> Result<String> {
+        self.get_deployment(deployment_id)
+            .await?
+            .project_id
+            .ok_or_else(|| anyhow!("Deployment {} has no project", deployment_id))
+    }
+
+    /// Raw build event response, as SSE lines or a JSON array.
+    ///
+    /// With `follow` the response keeps streaming until the build finishes.
+    pub async fn get_deployment_events(&self, deployment_id: &str, follow: bool) -> Result<reqwest::Response> {
+        let mut req = self
+            .team_get(format!("{}/v3/deployments/{}/events", API_BASE, deployment_id))
+            .query(&[("build", "1")]);
+
+        if follow {
+            req = req.query(&[("follow", "1")]);
+        }
+
+        let resp = req.send_tracked(&self.quota).await?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::from_response("Failed to fetch logs", resp).await.into());
+        }
+
+        Ok(resp)
+    }
+
+    pub async fn get_deployment_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        let text = self.get_deployment_events(deployment_id, false).await?.text().await?;
+        let mut logs = Vec::new();
+
+        for line in text.lines() {
+            if let Some(data) = line.strip_prefix("data: ") {
+                if let Ok(event) = serde_json::from_str::<serde_json::Value>(data) {
+                    if let Some(payload) = event.get("payload") {
+                        if let Some(text) = payload.get("text").and_then(|t| t.as_str()) {
+                            let is_error = text.to_lowercase().contains("error")
+                                || text.to_lowercase().contains("failed");
+
+                            logs.push(LogLine {
+                                timestamp: event
+                                    .get("created")
+                                    .and_then(|c| c.as_i64())
+                                    .unwrap_or(0),
+                                text: text.to_string(),
+                                is_error,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(logs)
+    }
+
     pub async fn get_token_info(&self) -> Result<TokenInfo> {
         let resp = self
             .client
             .get(format!("{}/v5/user/tokens/current", API_BASE))
-            .send()
+            .send_tracked(&self.quota)
             .await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to get token info: {}", resp.status()));
+            return Err(AppError::from_response("Failed to get token info", resp).await.into());
         }
 
         let token_resp: TokenInfoResponse = resp.json().await?;
@@ -114,11 +376,11 @@ impl VercelClient {
             .client
             .get(format!("{}/v2/teams", API_BASE))
             .query(&[("limit", "50")])
-            .send()
+            .send_tracked(&self.quota)
             .await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to list teams: {}", resp.status()));
+            return Err(AppError::from_response("Failed to list teams", resp).await.into());
         }
 
         let teams_resp: TeamsResponse = resp.json().await?;
@@ -129,11 +391,11 @@ impl VercelClient {
         let resp = self
             .client
             .get(format!("{}/v2/teams/{}", API_BASE, team_id))
-            .send()
+            .send_tracked(&self.quota)
             .await?;
 
         if !resp.status().is_success() {
-            return Err(anyhow!("Failed to get team: {}", resp.status()));
+            return Err(AppError::from_response("Failed to get team", resp).await.into());
         }
 
         let team: Team = resp.json().await?;
@@ -1,5 +1,7 @@
 pub mod client;
+pub mod provider;
 pub mod types;
 
 pub use client::*;
+pub use provider::*;
 pub use types::*;
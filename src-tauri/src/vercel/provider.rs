@@ -0,0 +1,308 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use crate::provider::{self, DeploymentPage, DeploymentProvider, UnifiedDeployment, UnifiedProject};
+use crate::state::CachedAccount;
+use super::client::VercelClient;
+use super::types::*;
+
+impl VercelClient {
+    pub(crate) fn to_unified(&self, d: Deployment) -> UnifiedDeployment {
+        let status = match d.state.as_ref().or(d.ready_state.as_ref()) {
+            Some(s) => format!("{:?}", s).to_uppercase(),
+            None => "UNKNOWN".to_string(),
+        };
+
+        UnifiedDeployment {
+            id: d.uid.clone(),
+            provider: "vercel".to_string(),
+            name: d.name.clone(),
+            url: Some(format!("https://{}", d.url)),
+            status,
+            created_at: d.created_at,
+            commit_message: d.meta.as_ref().and_then(|m| m.commit_message.clone()),
+            branch: d.meta.as_ref().and_then(|m| m.branch.clone()),
+            project_id: Some(d.name.clone()),
+            service_id: None,
+            git_author_login: d.meta.as_ref().and_then(|m| m.git_author_login.clone()),
+            team_slug: self.team_slug().map(|s| s.to_string()),
+            account_id: None,
+            queue_ms: provider::duration_ms(d.created_at, d.building_at),
+            build_ms: provider::duration_ms(d.building_at, d.ready),
+            environment: Some(d.target.clone().unwrap_or_else(|| "preview".to_string())),
+            dashboard_url: None,
+            logs_url: None,
+        }
+        .with_links()
+    }
+}
+
+#[async_trait]
+impl DeploymentProvider for VercelClient {
+    async fn validate_token(&self) -> Result<()> {
+        self.get_user().await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let projects = VercelClient::list_projects(self).await?;
+        Ok(projects
+            .into_iter()
+            .map(|p| UnifiedProject {
+                id: p.id,
+                provider: "vercel".to_string(),
+                name: p.name,
+                updated_at: p.updated_at,
+            })
+            .collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        // Fetch deployments at team level (not per project)
+        let deployments = VercelClient::list_deployments(self, None, limit).await?;
+        Ok(deployments.into_iter().map(|d| self.to_unified(d)).collect())
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let deployment = VercelClient::get_deployment(self, deployment_id).await?;
+        Ok(self.to_unified(deployment))
+    }
+
+    async fn list_deployments_page(&self, limit: u32, cursor: Option<String>) -> Result<DeploymentPage> {
+        let page = self.list_deployments_page(None, parse_cursor(cursor.as_deref())?, limit).await?;
+        Ok(DeploymentPage {
+            deployments: page.items.into_iter().map(|d| self.to_unified(d)).collect(),
+            next_cursor: page.next.map(|ts| ts.to_string()),
+        })
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        self.get_deployment_logs(deployment_id).await
+    }
+}
+
+/// One token polled across several Vercel teams
+pub struct VercelTeamsProvider {
+    clients: Vec<VercelClient>,
+}
+
+impl VercelTeamsProvider {
+    // Client of the team that owns a deployment
+    async fn find_client(&self, deployment_id: &str) -> Result<(&VercelClient, Deployment)> {
+        let (index, deployment) = find_owner(&self.clients, deployment_id).await?;
+        Ok((&self.clients[index], deployment))
+    }
+}
+
+// Ask every team for a deployment at once; only its owner has it
+async fn find_owner(clients: &[VercelClient], deployment_id: &str) -> Result<(usize, Deployment)> {
+    let results = join_all(clients.iter().map(|c| c.get_deployment(deployment_id))).await;
+    results
+        .into_iter()
+        .enumerate()
+        .find_map(|(index, r)| r.ok().map(|d| (index, d)))
+        .ok_or_else(|| anyhow!("Deployment {} not found in any team", deployment_id))
+}
+
+// Cursor key of a team's client; the personal scope has no team ID
+fn team_key(client: &VercelClient) -> String {
+    client.team_id().unwrap_or_default().to_string()
+}
+
+#[async_trait]
+impl DeploymentProvider for VercelTeamsProvider {
+    async fn validate_token(&self) -> Result<()> {
+        self.clients[0].get_user().await.map(|_| ())
+    }
+
+    async fn list_projects(&self) -> Result<Vec<UnifiedProject>> {
+        let results = join_all(self.clients.iter().map(DeploymentProvider::list_projects)).await;
+        Ok(merge_team_results(results)?.into_iter().flatten().collect())
+    }
+
+    async fn list_deployments(&self, limit: u32) -> Result<Vec<UnifiedDeployment>> {
+        let results = join_all(self.clients.iter().map(|c| DeploymentProvider::list_deployments(c, limit))).await;
+        let mut deployments: Vec<UnifiedDeployment> = merge_team_results(results)?.into_iter().flatten().collect();
+
+        // Newest first across teams
+        deployments.sort_by_key(|d| Reverse(d.created_at.unwrap_or(0)));
+        deployments.truncate(limit as usize);
+        Ok(deployments)
+    }
+
+    async fn get_deployment(&self, deployment_id: &str) -> Result<UnifiedDeployment> {
+        let (client, deployment) = self.find_client(deployment_id).await?;
+        Ok(client.to_unified(deployment))
+    }
+
+    async fn list_deployments_page(&self, limit: u32, cursor: Option<String>) -> Result<DeploymentPage> {
+        let cursors = parse_team_cursors(cursor.as_deref())?;
+
+        // Teams missing from a cursor start at their latest deployment;
+        // exhausted teams (`None`) are skipped
+        let active: Vec<(&VercelClient, PageCursor)> = self
+            .clients
+            .iter()
+            .filter_map(|c| match cursors.as_ref().and_then(|m| m.get(&team_key(c))) {
+                None => Some((c, PageCursor::Latest)),
+                Some(Some(ts)) => Some((c, PageCursor::Until(*ts))),
+                Some(None) => None,
+            })
+            .collect();
+
+        let results = join_all(active.iter().map(|&(c, cursor)| async move {
+            let page = c.list_deployments_page(None, cursor, limit).await?;
+            Ok((c, cursor, page))
+        }))
+        .await;
+        let pages = merge_team_results(results)?;
+
+        let mut deployments: Vec<(String, UnifiedDeployment)> = pages
+            .iter()
+            .flat_map(|(c, _, p)| p.items.iter().map(move |d| (team_key(c), c.to_unified(d.clone()))))
+            .collect();
+        deployments.sort_by_key(|(_, d)| Reverse(d.created_at.unwrap_or(0)));
+        deployments.truncate(limit as usize);
+
+        // Each team resumes after the oldest of its deployments shown here,
+        // so ones cut off by the merge come back on the next page
+        let mut next: HashMap<String, Option<i64>> = cursors.unwrap_or_default();
+        for (client, cursor, page) in &pages {
+            let key = team_key(client);
+            let oldest_shown = deployments.iter().filter(|(k, _)| *k == key).filter_map(|(_, d)| d.created_at).min();
+            let all_shown = deployments.iter().filter(|(k, _)| *k == key).count() == page.items.len();
+
+            let resume = match (oldest_shown, all_shown) {
+                (_, true) => page.next,
+                (Some(ts), false) => Some(ts),
+                // Nothing of this team made the cut, ask again from the same place
+                (None, false) => match cursor {
+                    PageCursor::Until(ts) => Some(*ts),
+                    _ => {
+                        next.remove(&key);
+                        continue;
+                    }
+                },
+            };
+            next.insert(key, resume);
+        }
+
+        let has_more = self
+            .clients
+            .iter()
+            .any(|c| !matches!(next.get(&team_key(c)), Some(None)));
+        let next_cursor = if has_more {
+            Some(serde_json::to_string(&next)?)
+        } else {
+            None
+        };
+
+        Ok(DeploymentPage {
+            deployments: deployments.into_iter().map(|(_, d)| d).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn fetch_logs(&self, deployment_id: &str) -> Result<Vec<LogLine>> {
+        let (client, _) = self.find_client(deployment_id).await?;
+        client.get_deployment_logs(deployment_id).await
+    }
+}
+
+// Cursors are the `pagination.next` timestamps Vercel hands out
+fn parse_cursor(cursor: Option<&str>) -> Result<PageCursor> {
+    match cursor {
+        Some(c) => c
+            .parse()
+            .map(PageCursor::Until)
+            .map_err(|_| anyhow!("Invalid cursor: {}", c)),
+        None => Ok(PageCursor::Latest),
+    }
+}
+
+// Team cursors are a JSON map from team ID to where its next page starts,
+// `null` once the team has no older deployments
+fn parse_team_cursors(cursor: Option<&str>) -> Result<Option<HashMap<String, Option<i64>>>> {
+    cursor
+        .map(|c| serde_json::from_str(c).map_err(|_| anyhow!("Invalid cursor: {}", c)))
+        .transpose()
+}
+
+// Skip teams that failed (e.g. membership revoked) unless all of them did
+fn merge_team_results<T>(results: Vec<Result<T>>) -> Result<Vec<T>> {
+    let mut merged = Vec::new();
+    let mut last_error = None;
+
+    for result in results {
+        match result {
+            Ok(value) => merged.push(value),
+            Err(e) => {
+                eprintln!("[VERCEL] Skipping team: {}", e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    match last_error {
+        Some(e) if merged.is_empty() => Err(e),
+        _ => Ok(merged),
+    }
+}
+
+/// Client scoped to the account's team
+pub fn create_client_for_account(account: &CachedAccount) -> Result<VercelClient> {
+    // Team slug for dashboard URLs (use team_slug if team, else username)
+    let team_slug = account.team_slug.clone().unwrap_or_else(|| account.username.clone());
+    Ok(VercelClient::new(account.token.clone())?
+        .with_team_slug(team_slug)
+        .with_team_id(account.team_id.clone())
+        .with_quota(account.quota.clone()))
+}
+
+/// Clients for the account's own scope followed by each included team
+pub fn create_team_clients(account: &CachedAccount) -> Result<Vec<VercelClient>> {
+    let mut clients = vec![create_client_for_account(account)?];
+
+    for team in &account.included_teams {
+        if account.team_id.as_deref() == Some(team.id.as_str()) {
+            continue;
+        }
+        clients.push(
+            VercelClient::new(account.token.clone())?
+                .with_team_slug(team.slug.clone())
+                .with_team_id(Some(team.id.clone()))
+                .with_quota(account.quota.clone()),
+        );
+    }
+
+    Ok(clients)
+}
+
+/// Client of the team that owns a deployment, straight from `team_slug`
+/// when it's known
+pub async fn find_deployment_client(
+    account: &CachedAccount,
+    deployment_id: &str,
+    team_slug: Option<&str>,
+) -> Result<VercelClient> {
+    let mut clients = create_team_clients(account)?;
+    if clients.len() == 1 {
+        return Ok(clients.remove(0));
+    }
+
+    if let Some(index) = clients.iter().position(|c| team_slug.is_some() && c.team_slug() == team_slug) {
+        return Ok(clients.swap_remove(index));
+    }
+
+    let (index, _) = find_owner(&clients, deployment_id).await?;
+    Ok(clients.swap_remove(index))
+}
+
+pub fn create_provider(account: &CachedAccount) -> Result<Box<dyn DeploymentProvider>> {
+    let mut clients = create_team_clients(account)?;
+    if clients.len() == 1 {
+        return Ok(Box::new(clients.remove(0)));
+    }
+    Ok(Box::new(VercelTeamsProvider { clients }))
+}
@@ -28,6 +28,38 @@ pub struct Project {
 #[serde(rename_all = "camelCase")]
 pub struct ProjectsResponse {
     pub projects: Vec<Project>,
+    #[serde(default)]
+    pub pagination: Option<Pagination>,
+}
+
+/// Cursor info returned by list endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    #[serde(default)]
+    pub count: u32,
+    // Timestamp to pass as `until`/`from` for the next (older) page
+    pub next: Option<i64>,
+    // Timestamp to pass as `since` for newer items
+    pub prev: Option<i64>,
+}
+
+/// Where a page of results starts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageCursor {
+    Latest,
+    // Items created before this timestamp
+    Until(i64),
+    // Items created after this timestamp
+    Since(i64),
+}
+
+/// One page of results plus the cursor for the next, older page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -61,6 +93,11 @@ pub struct Deployment {
     pub meta: Option<DeploymentMeta>,
     #[serde(default)]
     pub creator: Option<DeploymentCreator>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    // "production", or None for previews
+    #[serde(default)]
+    pub target: Option<String>,
 }
 
 impl Deployment {
@@ -148,6 +185,8 @@ pub struct TeamsResponse {
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentsResponse {
     pub deployments: Vec<Deployment>,
+    #[serde(default)]
+    pub pagination: Option<Pagination>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
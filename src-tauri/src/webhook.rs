@@ -0,0 +1,611 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use crate::provider::{self, UnifiedDeployment};
+use crate::state::{AppState, CachedAccount};
+use crate::tracker;
+use crate::tray;
+
+const DEFAULT_PORT: u16 = 8765;
+// Largest request accepted; deployment webhooks are a few KB
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+// Time a client gets to send its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Settings of the embedded webhook listener, stored in the keychain with the accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    pub port: u16,
+    // Listen on every interface instead of localhost only, for LAN relays
+    #[serde(default)]
+    pub listen_on_lan: bool,
+    // Secret of the Vercel webhook, used to check `x-vercel-signature`
+    #[serde(default)]
+    pub vercel_secret: Option<String>,
+    // Shared secret Railway webhook URLs must carry
+    #[serde(default)]
+    pub railway_secret: Option<String>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            listen_on_lan: false,
+            vercel_secret: None,
+            railway_secret: None,
+        }
+    }
+}
+
+/// Optional HTTP listener that turns Vercel and Railway deployment webhooks
+/// into the same status changes the poller produces
+#[derive(Default)]
+pub struct WebhookServer {
+    config: Mutex<WebhookConfig>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WebhookServer {
+    pub fn config(&self) -> WebhookConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: WebhookConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// Start listening if enabled, replacing a running listener
+    pub async fn restart(&self, app: AppHandle) -> Result<()> {
+        self.stop();
+
+        let config = self.config();
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let host = if config.listen_on_lan { "0.0.0.0" } else { "127.0.0.1" };
+        let listener = TcpListener::bind((host, config.port))
+            .await
+            .map_err(|e| anyhow!("Failed to listen on {}:{}: {}", host, config.port, e))?;
+        eprintln!("[WEBHOOK] Listening on {}:{}", host, config.port);
+
+        let task = tauri::async_runtime::spawn(serve(app, listener));
+        *self.task.lock().unwrap() = Some(task);
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+            eprintln!("[WEBHOOK] Stopped listening");
+        }
+    }
+}
+
+async fn serve(app: AppHandle, listener: TcpListener) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("[WEBHOOK] Accept failed: {}", e);
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(&app, stream).await {
+                eprintln!("[WEBHOOK] {}", e);
+            }
+        });
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    // Lowercase names
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+async fn handle_connection(app: &AppHandle, mut stream: TcpStream) -> Result<()> {
+    let (status, message) = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => handle_request(app, &request),
+        Ok(Err(e)) => (400, e.to_string()),
+        Err(_) => (408, "Request timed out".to_string()),
+    };
+
+    if status >= 400 {
+        eprintln!("[WEBHOOK] Responding {}: {}", status, message);
+    }
+
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        message.len(),
+        message
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+// Minimal HTTP/1.1 parsing: request line, headers and a Content-Length body
+async fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_REQUEST_BYTES {
+            return Err(anyhow!("Headers too large"));
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(anyhow!("Connection closed mid-request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let target = request_line.next().unwrap_or("/");
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(anyhow!("Body too large"));
+    }
+
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(anyhow!("Connection closed mid-body"));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, parse_query(query)),
+        None => (target, HashMap::new()),
+    };
+
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        query,
+        headers,
+        body,
+    })
+}
+
+// Percent-decoded, so secrets with reserved characters survive the URL
+fn parse_query(query: &str) -> HashMap<String, String> {
+    url::form_urlencoded::parse(query.as_bytes()).into_owned().collect()
+}
+
+fn handle_request(app: &AppHandle, request: &Request) -> (u16, String) {
+    if request.method != "POST" {
+        return (405, "Only POST is supported".to_string());
+    }
+
+    let config = app.state::<AppState>().webhook.config();
+    let result = match request.path.as_str() {
+        "/vercel" => verify_vercel(&config, request).and_then(|_| handle_vercel(app, &request.body)),
+        "/railway" => verify_railway(&config, request).and_then(|_| handle_railway(app, &request.body)),
+        _ => return (404, "Unknown webhook path".to_string()),
+    };
+
+    match result {
+        Ok(Handled::Ingested) => (200, "ok".to_string()),
+        Ok(Handled::Ignored(reason)) => (202, format!("ignored: {}", reason)),
+        Err(WebhookError::Unauthorized(message)) => (401, message),
+        Err(WebhookError::Invalid(message)) => (400, message),
+    }
+}
+
+// Valid events that don't concern any tracked deployment are still accepted,
+// so the sender doesn't retry them
+enum Handled {
+    Ingested,
+    Ignored(&'static str),
+}
+
+enum WebhookError {
+    Unauthorized(String),
+    Invalid(String),
+}
+
+fn verify_vercel(config: &WebhookConfig, request: &Request) -> Result<(), WebhookError> {
+    let secret = config
+        .vercel_secret
+        .as_deref()
+        .ok_or_else(|| WebhookError::Unauthorized("No Vercel webhook secret configured".to_string()))?;
+    let signature = request
+        .headers
+        .get("x-vercel-signature")
+        .ok_or_else(|| WebhookError::Unauthorized("Missing x-vercel-signature".to_string()))?;
+
+    if !signature_matches(secret.as_bytes(), &request.body, signature) {
+        return Err(WebhookError::Unauthorized("Invalid Vercel signature".to_string()));
+    }
+    Ok(())
+}
+
+// Railway doesn't sign webhooks, so the URL carries a shared secret
+// (`?secret=`), or a relay can send it as `x-webhook-secret`
+fn verify_railway(config: &WebhookConfig, request: &Request) -> Result<(), WebhookError> {
+    let secret = config
+        .railway_secret
+        .as_deref()
+        .ok_or_else(|| WebhookError::Unauthorized("No Railway webhook secret configured".to_string()))?;
+    let given = request
+        .headers
+        .get("x-webhook-secret")
+        .or_else(|| request.query.get("secret"))
+        .ok_or_else(|| WebhookError::Unauthorized("Missing webhook secret".to_string()))?;
+
+    if !constant_time_eq(secret.as_bytes(), given.as_bytes()) {
+        return Err(WebhookError::Unauthorized("Invalid webhook secret".to_string()));
+    }
+    Ok(())
+}
+
+// Vercel webhook body, see https://vercel.com/docs/webhooks/webhooks-api
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VercelEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    created_at: Option<i64>,
+    payload: VercelEventPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct VercelEventPayload {
+    deployment: VercelEventDeployment,
+    #[serde(default)]
+    team: Option<IdOnly>,
+    // "production", or None for previews
+    #[serde(default)]
+    target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VercelEventDeployment {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    meta: Option<crate::vercel::DeploymentMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdOnly {
+    #[serde(default)]
+    id: Option<String>,
+}
+
+fn handle_vercel(app: &AppHandle, body: &[u8]) -> Result<Handled, WebhookError> {
+    let event: VercelEvent =
+        serde_json::from_slice(body).map_err(|e| WebhookError::Invalid(format!("Invalid Vercel payload: {}", e)))?;
+
+    let status = match event.event_type.as_str() {
+        "deployment.created" => "QUEUED",
+        "deployment.succeeded" | "deployment.ready" => "READY",
+        "deployment.error" => "ERROR",
+        "deployment.canceled" => "CANCELED",
+        // Other events don't change a deployment's status
+        _ => return Ok(Handled::Ignored("not a deployment status event")),
+    };
+
+    let state = app.state::<AppState>();
+    let team_id = event.payload.team.and_then(|t| t.id);
+    let target = event.payload.target;
+    let account = match find_vercel_account(&state.get_all_accounts(), team_id.as_deref()) {
+        Some(account) => account,
+        None => return Ok(Handled::Ignored("no Vercel account for this team")),
+    };
+
+    let d = event.payload.deployment;
+    let meta = d.meta.as_ref();
+    let deployment = state.history.get(&d.id).unwrap_or_else(|| UnifiedDeployment {
+        id: d.id.clone(),
+        provider: "vercel".to_string(),
+        name: d.name.clone().unwrap_or_else(|| "Deployment".to_string()),
+        url: d.url.as_ref().map(|u| format!("https://{}", u)),
+        status: status.to_string(),
+        created_at: event.created_at,
+        commit_message: meta.and_then(|m| m.commit_message.clone()),
+        branch: meta.and_then(|m| m.branch.clone()),
+        project_id: d.name.clone(),
+        service_id: None,
+        git_author_login: meta.and_then(|m| m.git_author_login.clone()),
+        team_slug: account.team_slug.clone().or_else(|| Some(account.username.clone())),
+        account_id: None,
+        queue_ms: None,
+        build_ms: None,
+        environment: Some(target.unwrap_or_else(|| "preview".to_string())),
+        dashboard_url: None,
+        logs_url: None,
+    }
+    .with_links());
+
+    ingest(app, &account.id, deployment, status);
+    Ok(Handled::Ingested)
+}
+
+// Account whose own team or included teams match, personal accounts for team-less events
+fn find_vercel_account(accounts: &[CachedAccount], team_id: Option<&str>) -> Option<CachedAccount> {
+    accounts
+        .iter()
+        .filter(|a| a.provider == "vercel")
+        .find(|a| match team_id {
+            Some(id) => a.team_id.as_deref() == Some(id) || a.included_teams.iter().any(|t| t.id == id),
+            None => a.team_id.is_none(),
+        })
+        .cloned()
+}
+
+// Railway webhook body
+#[derive(Debug, Deserialize)]
+struct RailwayEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(default)]
+    project: Option<RailwayEventNamed>,
+    #[serde(default)]
+    service: Option<RailwayEventNamed>,
+    #[serde(default)]
+    environment: Option<RailwayEventNamed>,
+    #[serde(default)]
+    deployment: Option<RailwayEventDeployment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RailwayEventNamed {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RailwayEventDeployment {
+    id: String,
+    #[serde(default)]
+    meta: Option<crate::railway::RailwayDeploymentMeta>,
+}
+
+fn handle_railway(app: &AppHandle, body: &[u8]) -> Result<Handled, WebhookError> {
+    let event: RailwayEvent =
+        serde_json::from_slice(body).map_err(|e| WebhookError::Invalid(format!("Invalid Railway payload: {}", e)))?;
+
+    // Only deploy events carry a deployment status
+    let (d, status) = match (event.event_type.as_str(), event.deployment, event.status) {
+        ("DEPLOY", Some(d), Some(status)) => (d, status.to_uppercase()),
+        _ => return Ok(Handled::Ignored("not a deploy event")),
+    };
+
+    let state = app.state::<AppState>();
+    let project_id = event.project.as_ref().map(|p| p.id.clone());
+    // Only projects already seen through an account can be attributed to it
+    let account_id = match project_id
+        .as_deref()
+        .and_then(|id| state.history.account_for_project("railway", id))
+    {
+        Some(account_id) => account_id,
+        None => return Ok(Handled::Ignored("no Railway account for this project")),
+    };
+
+    let name = match (&event.project, &event.service) {
+        (Some(p), Some(s)) => format!("{} / {}", p.name.as_deref().unwrap_or("Project"), s.name.as_deref().unwrap_or("Service")),
+        _ => "Deployment".to_string(),
+    };
+    let meta = d.meta.as_ref();
+
+    let deployment = state.history.get(&d.id).unwrap_or_else(|| UnifiedDeployment {
+        id: d.id.clone(),
+        provider: "railway".to_string(),
+        name,
+        url: None,
+        status: status.clone(),
+        created_at: provider::parse_timestamp(event.timestamp.as_deref()),
+        commit_message: meta.and_then(|m| m.commit_message.clone()),
+        branch: meta.and_then(|m| m.branch.clone()),
+        project_id,
+        service_id: event.service.map(|s| s.id),
+        git_author_login: None,
+        team_slug: None,
+        account_id: None,
+        queue_ms: None,
+        build_ms: None,
+        environment: event.environment.and_then(|e| e.name),
+        dashboard_url: None,
+        logs_url: None,
+    }
+    .with_links());
+
+    ingest(app, &account_id, deployment, &status);
+    Ok(Handled::Ingested)
+}
+
+// Same steps as a poll: record, diff against the last known status, publish
+fn ingest(app: &AppHandle, account_id: &str, mut deployment: UnifiedDeployment, status: &str) {
+    deployment.status = status.to_string();
+    deployment.account_id = Some(account_id.to_string());
+    eprintln!("[WEBHOOK] {} is now {}", deployment.id, status);
+
+    let state = app.state::<AppState>();
+    let deployments = [deployment];
+    state.history.record(&deployments);
+    let changes = state.tracker.observe(account_id, &deployments);
+    tracker::publish_changes(app, &changes);
+    tray::refresh(app);
+
+    // Poll right away for the details a webhook doesn't carry
+    state.scheduler.wake();
+}
+
+// Check a hex HMAC-SHA1 of the body, which is what Vercel signs webhooks with
+fn signature_matches(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let signature = match from_hex(signature) {
+        Some(signature) => signature,
+        None => return false,
+    };
+    let mut mac = match Hmac::<Sha1>::new_from_slice(secret) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Compare without bailing at the first difference, so timing doesn't leak the secret
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test cases from RFC 2202, section 3
+    #[test]
+    fn signature_matches_rfc_2202_vectors() {
+        let cases: [(&[u8], &[u8], &str); 5] = [
+            (&[0x0b; 20], b"Hi There", "b617318655057264e28bc0b6fb378c8ef146be00"),
+            (b"Jefe", b"what do ya want for nothing?", "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"),
+            (&[0xaa; 20], &[0xdd; 50], "125d7342b9ac11cd91a39af48aa17b4f63f175d3"),
+            (
+                &[0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+            ),
+            (
+                &[0xaa; 80],
+                b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+                "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+            ),
+        ];
+
+        for (key, data, digest) in cases {
+            assert!(signature_matches(key, data, digest), "{} should match", digest);
+            assert!(signature_matches(key, data, &digest.to_uppercase()));
+        }
+    }
+
+    #[test]
+    fn signature_mismatch_is_rejected() {
+        assert!(!signature_matches(b"Jefe", b"what do ya want for nothing?", "effcdf6ae5eb2fa2d27416d5f184df9c259a7c7a"));
+        assert!(!signature_matches(b"Jefe", b"what do ya want for nothing?", "effcdf6a"));
+        assert!(!signature_matches(b"Jefe", b"what do ya want for nothing?", "not hex"));
+        assert!(!signature_matches(b"Jefe", b"what do ya want for nothing?", ""));
+    }
+
+    fn vercel_account(id: &str, team_id: Option<&str>, included: &[&str]) -> CachedAccount {
+        CachedAccount {
+            id: id.to_string(),
+            username: id.to_string(),
+            email: String::new(),
+            name: None,
+            scope_type: if team_id.is_some() { "team" } else { "user" }.to_string(),
+            team_name: None,
+            team_slug: None,
+            team_id: team_id.map(str::to_string),
+            token: String::new(),
+            provider: "vercel".to_string(),
+            provider_account_id: None,
+            included_teams: included
+                .iter()
+                .map(|t| crate::state::TeamScope { id: t.to_string(), slug: t.to_string(), name: t.to_string() })
+                .collect(),
+            quota: Default::default(),
+        }
+    }
+
+    #[test]
+    fn find_vercel_account_matches_owner_only() {
+        let accounts = [
+            vercel_account("personal", None, &["team_included"]),
+            vercel_account("team", Some("team_own"), &[]),
+        ];
+        let found = |team_id| find_vercel_account(&accounts, team_id).map(|a| a.id);
+
+        assert_eq!(found(Some("team_own")).as_deref(), Some("team"));
+        assert_eq!(found(Some("team_included")).as_deref(), Some("personal"));
+        assert_eq!(found(None).as_deref(), Some("personal"));
+        assert_eq!(found(Some("team_other")), None);
+    }
+
+    #[test]
+    fn parse_query_decodes_values() {
+        let query = parse_query("secret=a%2Bb%3Dc%26d&note=hello+world&flag");
+
+        assert_eq!(query.get("secret").map(String::as_str), Some("a+b=c&d"));
+        assert_eq!(query.get("note").map(String::as_str), Some("hello world"));
+        assert_eq!(query.get("flag").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn from_hex_decodes_pairs() {
+        assert_eq!(from_hex("00ff10"), Some(vec![0x00, 0xff, 0x10]));
+        assert_eq!(from_hex("0102FE"), Some(vec![1, 2, 254]));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+}
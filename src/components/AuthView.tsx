@@ -1,10 +1,12 @@
 import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { KeyRound, ExternalLink, Loader2, Train, ChevronDown } from 'lucide-react';
-import type { Provider, Account } from '../types';
+import { KeyRound, ExternalLink, Loader2, Train, ChevronDown, Cloud } from 'lucide-react';
+import { errorMessage, PROVIDER_NAMES, type Provider, type Account } from '../types';
 
 type RailwayTokenType = 'workspace' | 'project';
 
+const PROVIDERS: Provider[] = ['vercel', 'railway', 'netlify', 'render', 'fly', 'cloudflare'];
+
 interface AuthViewProps {
   onSuccess: (account: Account) => void;
 }
@@ -13,6 +15,7 @@ export function AuthView({ onSuccess }: AuthViewProps) {
   const [token, setToken] = useState('');
   const [provider, setProvider] = useState<Provider>('vercel');
   const [railwayTokenType, setRailwayTokenType] = useState<RailwayTokenType>('workspace');
+  const [cloudflareAccountId, setCloudflareAccountId] = useState('');
   const [error, setError] = useState<string | null>(null);
   const [isSubmitting, setIsSubmitting] = useState(false);
 
@@ -22,6 +25,10 @@ export function AuthView({ onSuccess }: AuthViewProps) {
       setError('Please enter a token');
       return;
     }
+    if (provider === 'cloudflare' && !cloudflareAccountId.trim()) {
+      setError('Please enter your Cloudflare account ID');
+      return;
+    }
 
     setError(null);
     setIsSubmitting(true);
@@ -34,8 +41,15 @@ export function AuthView({ onSuccess }: AuthViewProps) {
           token: token.trim(),
           tokenType: railwayTokenType
         });
-      } else {
+      } else if (provider === 'cloudflare') {
+        account = await invoke<Account>('add_cloudflare_account', {
+          token: token.trim(),
+          cloudflareAccountId: cloudflareAccountId.trim()
+        });
+      } else if (provider === 'vercel') {
         account = await invoke<Account>('add_account', { token: token.trim() });
+      } else {
+        account = await invoke<Account>(`add_${provider}_account`, { token: token.trim() });
       }
 
       // Set as active account
@@ -44,18 +58,14 @@ export function AuthView({ onSuccess }: AuthViewProps) {
       onSuccess(account);
     } catch (err) {
       console.error('Save token error:', err);
-      setError(String(err));
+      setError(errorMessage(err));
       setIsSubmitting(false);
     }
   };
 
   const openTokensPage = async () => {
     try {
-      if (provider === 'railway') {
-        await invoke('open_railway_tokens');
-      } else {
-        await invoke('open_vercel_tokens');
-      }
+      await invoke(`open_${provider}_tokens`);
     } catch (err) {
       console.error('Failed to open tokens page:', err);
     }
@@ -88,12 +98,14 @@ export function AuthView({ onSuccess }: AuthViewProps) {
           }}>
             {provider === 'railway' ? (
               <Train style={{ width: 32, height: 32, color: '#fff' }} />
-            ) : (
+            ) : provider === 'vercel' ? (
               <KeyRound style={{ width: 32, height: 32, color: '#fff' }} />
+            ) : (
+              <Cloud style={{ width: 32, height: 32, color: '#fff' }} />
             )}
           </div>
           <h1 style={{ fontSize: 20, fontWeight: 600, color: '#fff', marginBottom: 8 }}>
-            Connect to {provider === 'railway' ? 'Railway' : 'Vercel'}
+            Connect to {PROVIDER_NAMES[provider]}
           </h1>
           <p style={{ fontSize: 14, color: '#888' }}>
             Enter your Personal Access Token to get started
@@ -102,59 +114,46 @@ export function AuthView({ onSuccess }: AuthViewProps) {
 
         {/* Provider Toggle */}
         <div style={{
-          display: 'flex',
+          display: 'grid',
+          gridTemplateColumns: 'repeat(3, 1fr)',
           gap: 8,
           marginBottom: 16,
           padding: 4,
           backgroundColor: '#1a1a1a',
           borderRadius: 8,
         }}>
-          <button
-            type="button"
-            onClick={() => setProvider('vercel')}
-            style={{
-              flex: 1,
-              padding: '8px 12px',
-              backgroundColor: provider === 'vercel' ? '#333' : 'transparent',
-              color: provider === 'vercel' ? '#fff' : '#888',
-              border: 'none',
-              borderRadius: 6,
-              cursor: 'pointer',
-              fontSize: 13,
-              fontWeight: 500,
-              display: 'flex',
-              alignItems: 'center',
-              justifyContent: 'center',
-              gap: 6,
-            }}
-          >
-            <svg width="14" height="14" viewBox="0 0 76 65" fill="currentColor">
-              <path d="M37.5274 0L75.0548 65H0L37.5274 0Z" />
-            </svg>
-            Vercel
-          </button>
-          <button
-            type="button"
-            onClick={() => setProvider('railway')}
-            style={{
-              flex: 1,
-              padding: '8px 12px',
-              backgroundColor: provider === 'railway' ? '#333' : 'transparent',
-              color: provider === 'railway' ? '#fff' : '#888',
-              border: 'none',
-              borderRadius: 6,
-              cursor: 'pointer',
-              fontSize: 13,
-              fontWeight: 500,
-              display: 'flex',
-              alignItems: 'center',
-              justifyContent: 'center',
-              gap: 6,
-            }}
-          >
-            <Train style={{ width: 14, height: 14 }} />
-            Railway
-          </button>
+          {PROVIDERS.map((p) => (
+            <button
+              key={p}
+              type="button"
+              onClick={() => setProvider(p)}
+              style={{
+                padding: '8px 12px',
+                backgroundColor: provider === p ? '#333' : 'transparent',
+                color: provider === p ? '#fff' : '#888',
+                border: 'none',
+                borderRadius: 6,
+                cursor: 'pointer',
+                fontSize: 13,
+                fontWeight: 500,
+                display: 'flex',
+                alignItems: 'center',
+                justifyContent: 'center',
+                gap: 6,
+              }}
+            >
+              {p === 'vercel' ? (
+                <svg width="14" height="14" viewBox="0 0 76 65" fill="currentColor">
+                  <path d="M37.5274 0L75.0548 65H0L37.5274 0Z" />
+                </svg>
+              ) : p === 'railway' ? (
+                <Train style={{ width: 14, height: 14 }} />
+              ) : (
+                <Cloud style={{ width: 14, height: 14 }} />
+              )}
+              {PROVIDER_NAMES[p]}
+            </button>
+          ))}
         </div>
 
         {/* Railway Token Type Selector */}
@@ -211,6 +210,37 @@ export function AuthView({ onSuccess }: AuthViewProps) {
           </div>
         )}
 
+        {/* Cloudflare Account ID */}
+        {provider === 'cloudflare' && (
+          <div style={{ marginBottom: 16 }}>
+            <label style={{
+              display: 'block',
+              fontSize: 12,
+              color: '#888',
+              marginBottom: 8,
+            }}>
+              Account ID
+            </label>
+            <input
+              type="text"
+              value={cloudflareAccountId}
+              onChange={(e) => setCloudflareAccountId(e.target.value)}
+              placeholder="Found in the dashboard URL"
+              disabled={isSubmitting}
+              style={{
+                width: '100%',
+                padding: '10px 12px',
+                backgroundColor: '#222',
+                border: '1px solid #333',
+                borderRadius: 8,
+                color: '#fff',
+                fontSize: 14,
+                outline: 'none',
+              }}
+            />
+          </div>
+        )}
+
         <form onSubmit={handleSubmit}>
           <div style={{ marginBottom: 16 }}>
             <input
@@ -283,7 +313,7 @@ export function AuthView({ onSuccess }: AuthViewProps) {
           }}
         >
           <ExternalLink style={{ width: 16, height: 16 }} />
-          Create a token on {provider === 'railway' ? 'Railway' : 'Vercel'}
+          Create a token on {PROVIDER_NAMES[provider]}
         </button>
       </div>
     </div>
@@ -1,13 +1,15 @@
 import { useEffect, useState, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { RefreshCw, Settings, GitBranch, Loader2, Train, Copy, ExternalLink } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
+import { RefreshCw, Settings, GitBranch, Loader2, Train, Copy, ExternalLink, Cloud } from 'lucide-react';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import { PROVIDER_NAMES, type DeploymentPhase, type Provider } from '../types';
 import './DeploymentsList.css';
 
 // Unified deployment from backend
 interface UnifiedDeployment {
   id: string;
-  provider: 'vercel' | 'railway';
+  provider: Provider;
   name: string;
   url: string | null;
   status: string;
@@ -19,12 +21,54 @@ interface UnifiedDeployment {
   gitAuthorLogin: string | null;
   teamSlug: string | null;
   accountId: string | null;
+  queueMs: number | null;
+  buildMs: number | null;
+  // Links built by the backend for each provider
+  dashboardUrl: string | null;
+  logsUrl: string | null;
+}
+
+type AccountFetchStatus =
+  | { kind: 'ok' }
+  | { kind: 'authFailed'; message: string }
+  | { kind: 'rateLimited'; message: string }
+  | { kind: 'timedOut' }
+  | { kind: 'failed'; message: string };
+
+interface AccountFetchResult {
+  accountId: string;
+  provider: string;
+  status: AccountFetchStatus;
+}
+
+interface AllDeploymentsResponse {
+  deployments: UnifiedDeployment[];
+  accounts: AccountFetchResult[];
+  // From local history; fresh results follow in a `deployments-refreshed` event
+  cached: boolean;
+}
+
+// Payload of the `deployment-status-changed` event
+interface DeploymentStatusChange {
+  deployment: UnifiedDeployment;
+  previous: DeploymentPhase | null;
+  current: DeploymentPhase;
 }
 
 interface Props {
   onOpenSettings: () => void;
 }
 
+function describeFailure(result: AccountFetchResult): string {
+  switch (result.status.kind) {
+    case 'authFailed': return `${result.provider}: token rejected`;
+    case 'rateLimited': return `${result.provider}: rate limited`;
+    case 'timedOut': return `${result.provider}: timed out`;
+    case 'failed': return `${result.provider}: ${result.status.message}`;
+    default: return '';
+  }
+}
+
 function mapStatus(status: string): string {
   // Normalize status strings
   const s = status.toUpperCase();
@@ -85,7 +129,7 @@ export function DeploymentsList({ onOpenSettings }: Props) {
   const [contextMenu, setContextMenu] = useState<ContextMenu | null>(null);
   const [copyingId, setCopyingId] = useState<string | null>(null);
   const [copyMessage, setCopyMessage] = useState<string>('');
-  const previousStatusRef = useRef<Map<string, string>>(new Map());
+  const [failedAccounts, setFailedAccounts] = useState<AccountFetchResult[]>([]);
   const isFirstFetchRef = useRef(true);
   const limitRef = useRef(INITIAL_LIMIT); // Keep limit in ref to avoid stale closure
   const pollIntervalRef = useRef<ReturnType<typeof setInterval> | null>(null);
@@ -105,73 +149,8 @@ export function DeploymentsList({ onOpenSettings }: Props) {
       setIsLoadingMore(true);
     }
     try {
-      const data = await invoke<UnifiedDeployment[]>('list_all_deployments', { limit: currentLimit });
-      console.log('All Deployments:', JSON.stringify(data, null, 2));
-
-      // Check if there might be more deployments
-      setHasMore(data.length >= currentLimit);
-
-      // Check for status changes (only after first fetch)
-      if (!isFirstFetchRef.current) {
-        for (const d of data) {
-          const prevStatus = previousStatusRef.current.get(d.id);
-          const currentStatus = mapStatus(d.status);
-
-          if (prevStatus && prevStatus !== currentStatus) {
-            // Status changed - send notification with project name, branch, and commit
-            const commitTitle = d.commitMessage || 'No commit message';
-            const branchLine = d.branch ? `${d.branch}: ` : '';
-
-            if (currentStatus === 'READY' && prevStatus === 'BUILDING') {
-              await invoke('send_deployment_notification', {
-                title: `${d.name} Deployment Successful`,
-                body: `${branchLine}${commitTitle}`
-              });
-            } else if (currentStatus === 'ERROR') {
-              await invoke('send_deployment_notification', {
-                title: `${d.name} Deployment Failed`,
-                body: `${branchLine}${commitTitle}`
-              });
-            }
-          }
-
-          previousStatusRef.current.set(d.id, currentStatus);
-        }
-      } else {
-        // Initialize status map on first fetch
-        for (const d of data) {
-          previousStatusRef.current.set(d.id, mapStatus(d.status));
-        }
-        isFirstFetchRef.current = false;
-      }
-
-      setDeployments(data);
-
-      // Update tray icon based on building status
-      const buildingDeployment = data.find(d => {
-        const s = mapStatus(d.status);
-        return s === 'BUILDING' || s === 'QUEUED';
-      });
-      const hasBuilding = !!buildingDeployment;
-      await invoke('update_tray_status', {
-        isBuilding: hasBuilding,
-        buildingProject: buildingDeployment?.name || null
-      });
-
-      // Only poll when building, otherwise stop polling
-      if (hasBuilding !== hasBuildingRef.current) {
-        hasBuildingRef.current = hasBuilding;
-        if (hasBuilding) {
-          setupPolling(BUILDING_POLL_INTERVAL);
-        } else {
-          // Stop polling when not building
-          if (pollIntervalRef.current) {
-            clearInterval(pollIntervalRef.current);
-            pollIntervalRef.current = null;
-          }
-        }
-      }
-
+      const response = await invoke<AllDeploymentsResponse>('list_all_deployments', { limit: currentLimit });
+      await applyResponse(response, currentLimit);
     } catch (err) {
       console.error('Failed to fetch deployments:', err);
     } finally {
@@ -181,6 +160,60 @@ export function DeploymentsList({ onOpenSettings }: Props) {
     }
   };
 
+  const applyResponse = async (response: AllDeploymentsResponse, currentLimit: number) => {
+    const data = response.deployments;
+    console.log('All Deployments:', JSON.stringify(data, null, 2));
+    // Cached responses don't know how each account is doing yet
+    if (!response.cached) {
+      setFailedAccounts(response.accounts.filter(a => a.status.kind !== 'ok'));
+    }
+
+    // Check if there might be more deployments
+    setHasMore(data.length >= currentLimit);
+
+    // Status change notifications are sent by the backend
+    isFirstFetchRef.current = false;
+
+    setDeployments(data);
+
+    // Update tray icon based on building status
+    const buildingDeployment = data.find(d => {
+      const s = mapStatus(d.status);
+      return s === 'BUILDING' || s === 'QUEUED';
+    });
+    const hasBuilding = !!buildingDeployment;
+    await invoke('update_tray_status', {
+      isBuilding: hasBuilding,
+      buildingProject: buildingDeployment?.name || null
+    });
+
+    // Only poll when building, otherwise stop polling
+    if (hasBuilding !== hasBuildingRef.current) {
+      hasBuildingRef.current = hasBuilding;
+      if (hasBuilding) {
+        setupPolling(BUILDING_POLL_INTERVAL);
+      } else {
+        // Stop polling when not building
+        if (pollIntervalRef.current) {
+          clearInterval(pollIntervalRef.current);
+          pollIntervalRef.current = null;
+        }
+      }
+    }
+  };
+
+  const applyStatusChange = (deployment: UnifiedDeployment) => {
+    setDeployments(prev => {
+      if (prev.some(d => d.id === deployment.id)) {
+        return prev.map(d => d.id === deployment.id ? deployment : d);
+      }
+      // New deployments go in by creation time, keeping the list at its limit
+      return [...prev, deployment]
+        .sort((a, b) => (b.createdAt ?? 0) - (a.createdAt ?? 0))
+        .slice(0, limitRef.current);
+    });
+  };
+
   const setupPolling = (interval: number) => {
     if (pollIntervalRef.current) {
       clearInterval(pollIntervalRef.current);
@@ -208,7 +241,19 @@ export function DeploymentsList({ onOpenSettings }: Props) {
 
     document.addEventListener('visibilitychange', handleVisibilityChange);
 
+    // Patch the changed deployment in place; full lists arrive with `deployments-refreshed`
+    const unlistenStatus = listen<DeploymentStatusChange>('deployment-status-changed', event => {
+      applyStatusChange(event.payload.deployment);
+    });
+
+    // Fresh results after a cached response
+    const unlistenRefreshed = listen<AllDeploymentsResponse>('deployments-refreshed', event => {
+      applyResponse(event.payload, limitRef.current);
+    });
+
     return () => {
+      unlistenStatus.then(unlisten => unlisten());
+      unlistenRefreshed.then(unlisten => unlisten());
       if (pollIntervalRef.current) {
         clearInterval(pollIntervalRef.current);
       }
@@ -267,47 +312,25 @@ export function DeploymentsList({ onOpenSettings }: Props) {
     }
   };
 
-  const openDeploymentUrl = async (d: UnifiedDeployment) => {
-    let url: string;
-    if (d.provider === 'railway') {
-      url = 'https://railway.com/dashboard';
-      if (d.projectId) {
-        url = `https://railway.com/project/${d.projectId}`;
-      }
-    } else {
-      // Vercel - open deployed URL if available
-      if (d.url) {
-        url = d.url;
-      } else {
-        url = `https://vercel.com/${d.teamSlug}/${d.name}/${d.id}`;
-      }
-    }
+  const openUrl = async (url: string | null) => {
+    if (!url) return;
+    // Some providers report bare hostnames
+    const href = /^https?:\/\//.test(url) ? url : `https://${url}`;
     try {
       const opener = await import('@tauri-apps/plugin-opener');
-      await opener.openUrl(url);
+      await opener.openUrl(href);
     } catch {
-      window.open(url, '_blank');
+      window.open(href, '_blank');
     }
   };
 
-  const openDeploymentPage = async (d: UnifiedDeployment) => {
-    let url: string;
-    if (d.provider === 'railway') {
-      url = 'https://railway.com/dashboard';
-      if (d.projectId) {
-        url = `https://railway.com/project/${d.projectId}`;
-      }
-    } else {
-      // Vercel deployment page
-      url = `https://vercel.com/${d.teamSlug}/${d.name}/${d.id}`;
-    }
-    try {
-      const opener = await import('@tauri-apps/plugin-opener');
-      await opener.openUrl(url);
-    } catch {
-      window.open(url, '_blank');
-    }
-  };
+  // Deployed site, or the dashboard when there is none
+  const openDeploymentUrl = (d: UnifiedDeployment) => openUrl(d.url ?? d.dashboardUrl);
+
+  // The deployment's page in the provider dashboard
+  const openDeploymentPage = (d: UnifiedDeployment) => openUrl(d.logsUrl ?? d.dashboardUrl);
+
+  const openDashboard = (d: UnifiedDeployment) => openUrl(d.dashboardUrl);
 
   const handleClick = async (d: UnifiedDeployment) => {
     const status = mapStatus(d.status);
@@ -360,6 +383,14 @@ export function DeploymentsList({ onOpenSettings }: Props) {
         </div>
       </div>
 
+      {failedAccounts.length > 0 && (
+        <div className="account-errors" title={failedAccounts.map(describeFailure).join('\n')}>
+          {failedAccounts.length === 1
+            ? describeFailure(failedAccounts[0])
+            : `${failedAccounts.length} accounts failed to load`}
+        </div>
+      )}
+
       {/* Deployments List */}
       <div className="deployments-list">
         {isLoading ? (
@@ -411,10 +442,14 @@ export function DeploymentsList({ onOpenSettings }: Props) {
                   {/* Provider icon before project name */}
                   {d.provider === 'railway' ? (
                     <Train style={{ width: 10, height: 10, color: '#a78bfa' }} />
-                  ) : (
+                  ) : d.provider === 'vercel' ? (
                     <svg width="10" height="10" viewBox="0 0 76 65" fill="#888">
                       <path d="M37.5274 0L75.0548 65H0L37.5274 0Z" />
                     </svg>
+                  ) : (
+                    <span title={PROVIDER_NAMES[d.provider]} style={{ display: 'inline-flex' }}>
+                      <Cloud style={{ width: 10, height: 10, color: '#888' }} />
+                    </span>
                   )}
 
                   <span className="project-name">{d.name}</span>
@@ -474,29 +509,29 @@ export function DeploymentsList({ onOpenSettings }: Props) {
               Open deployment URL
             </button>
           )}
-          <button
-            className="context-menu-item"
-            onClick={() => {
-              openDeploymentPage(contextMenu.deployment);
-              closeContextMenu();
-            }}
-          >
-            <ExternalLink style={{ width: 12, height: 12 }} />
-            Open {contextMenu.deployment.provider === 'railway' ? 'Railway' : 'Vercel'} dashboard
-          </button>
-          {contextMenu.deployment.provider === 'vercel' && (
+          {contextMenu.deployment.dashboardUrl && (
             <button
               className="context-menu-item"
               onClick={() => {
-                const isError = mapStatus(contextMenu.deployment.status) === 'ERROR';
-                copyLogs(contextMenu.deployment, isError);
+                openDashboard(contextMenu.deployment);
                 closeContextMenu();
               }}
             >
-              <Copy style={{ width: 12, height: 12 }} />
-              {mapStatus(contextMenu.deployment.status) === 'ERROR' ? 'Copy errors' : 'Copy logs'}
+              <ExternalLink style={{ width: 12, height: 12 }} />
+              Open {PROVIDER_NAMES[contextMenu.deployment.provider]} dashboard
             </button>
           )}
+          <button
+            className="context-menu-item"
+            onClick={() => {
+              const isError = mapStatus(contextMenu.deployment.status) === 'ERROR';
+              copyLogs(contextMenu.deployment, isError);
+              closeContextMenu();
+            }}
+          >
+            <Copy style={{ width: 12, height: 12 }} />
+            {mapStatus(contextMenu.deployment.status) === 'ERROR' ? 'Copy errors' : 'Copy logs'}
+          </button>
         </div>
       )}
     </div>
@@ -1,10 +1,12 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { ChevronLeft, Plus, Trash2, ExternalLink, Loader2, Users, Train, ChevronDown, Pencil, Check, X } from 'lucide-react';
-import type { Provider } from '../types';
+import { ChevronLeft, Plus, Trash2, ExternalLink, Loader2, Users, Train, ChevronDown, Pencil, Check, X, Cloud } from 'lucide-react';
+import { errorMessage, PROVIDER_NAMES, type Provider } from '../types';
 
 type RailwayTokenType = 'workspace' | 'project';
 
+const PROVIDERS: Provider[] = ['vercel', 'railway', 'netlify', 'render', 'fly', 'cloudflare'];
+
 interface Account {
   id: string;
   username: string;
@@ -24,6 +26,7 @@ export function Settings({ onBack }: Props) {
   const [isAddingAccount, setIsAddingAccount] = useState(false);
   const [addingProvider, setAddingProvider] = useState<Provider>('vercel');
   const [railwayTokenType, setRailwayTokenType] = useState<RailwayTokenType>('workspace');
+  const [cloudflareAccountId, setCloudflareAccountId] = useState('');
   const [newToken, setNewToken] = useState('');
   const [isSubmitting, setIsSubmitting] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -46,6 +49,10 @@ export function Settings({ onBack }: Props) {
   const handleAddAccount = async (e: React.FormEvent) => {
     e.preventDefault();
     if (!newToken.trim()) return;
+    if (addingProvider === 'cloudflare' && !cloudflareAccountId.trim()) {
+      setError('Enter your Cloudflare account ID');
+      return;
+    }
 
     setIsSubmitting(true);
     setError(null);
@@ -57,14 +64,22 @@ export function Settings({ onBack }: Props) {
           token: newToken.trim(),
           tokenType: railwayTokenType
         });
-      } else {
+      } else if (addingProvider === 'cloudflare') {
+        account = await invoke<Account>('add_cloudflare_account', {
+          token: newToken.trim(),
+          cloudflareAccountId: cloudflareAccountId.trim()
+        });
+      } else if (addingProvider === 'vercel') {
         account = await invoke<Account>('add_account', { token: newToken.trim() });
+      } else {
+        account = await invoke<Account>(`add_${addingProvider}_account`, { token: newToken.trim() });
       }
       setAccounts(prev => [...prev.filter(a => a.id !== account.id), account]);
       setNewToken('');
+      setCloudflareAccountId('');
       setIsAddingAccount(false);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setIsSubmitting(false);
     }
@@ -107,11 +122,7 @@ export function Settings({ onBack }: Props) {
 
   const openTokensPage = async (provider: Provider) => {
     try {
-      if (provider === 'railway') {
-        await invoke('open_railway_tokens');
-      } else {
-        await invoke('open_vercel_tokens');
-      }
+      await invoke(`open_${provider}_tokens`);
     } catch (err) {
       console.error('Failed to open tokens page:', err);
     }
@@ -204,6 +215,8 @@ export function Settings({ onBack }: Props) {
                 }}>
                   {account.provider === 'railway' ? (
                     <Train style={{ width: 14, height: 14, color: '#8b5cf6' }} />
+                  ) : account.provider !== 'vercel' ? (
+                    <Cloud style={{ width: 14, height: 14, color: account.scopeType === 'team' ? '#3b82f6' : '#666' }} />
                   ) : account.scopeType === 'team' ? (
                     <Users style={{ width: 14, height: 14, color: '#3b82f6' }} />
                   ) : (
@@ -351,61 +364,51 @@ export function Settings({ onBack }: Props) {
             <form onSubmit={handleAddAccount}>
               {/* Provider Toggle */}
               <div style={{
-                display: 'flex',
+                display: 'grid',
+                gridTemplateColumns: 'repeat(3, 1fr)',
                 gap: 4,
                 marginBottom: 8,
                 padding: 3,
                 backgroundColor: 'rgba(0,0,0,0.3)',
                 borderRadius: 6,
               }}>
-                <button
-                  type="button"
-                  onClick={() => setAddingProvider('vercel')}
-                  style={{
-                    flex: 1,
-                    padding: '6px 8px',
-                    backgroundColor: addingProvider === 'vercel' ? 'rgba(255,255,255,0.1)' : 'transparent',
-                    color: addingProvider === 'vercel' ? '#e5e5e5' : '#666',
-                    border: 'none',
-                    borderRadius: 4,
-                    cursor: 'pointer',
-                    fontSize: 10,
-                    fontWeight: 500,
-                    display: 'flex',
-                    alignItems: 'center',
-                    justifyContent: 'center',
-                    gap: 4,
-                    fontFamily: 'ui-monospace, SFMono-Regular, "SF Mono", Menlo, monospace',
-                  }}
-                >
-                  <svg width="10" height="10" viewBox="0 0 76 65" fill="currentColor">
-                    <path d="M37.5274 0L75.0548 65H0L37.5274 0Z" />
-                  </svg>
-                  Vercel
-                </button>
-                <button
-                  type="button"
-                  onClick={() => setAddingProvider('railway')}
-                  style={{
-                    flex: 1,
-                    padding: '6px 8px',
-                    backgroundColor: addingProvider === 'railway' ? 'rgba(139, 92, 246, 0.2)' : 'transparent',
-                    color: addingProvider === 'railway' ? '#a78bfa' : '#666',
-                    border: 'none',
-                    borderRadius: 4,
-                    cursor: 'pointer',
-                    fontSize: 10,
-                    fontWeight: 500,
-                    display: 'flex',
-                    alignItems: 'center',
-                    justifyContent: 'center',
-                    gap: 4,
-                    fontFamily: 'ui-monospace, SFMono-Regular, "SF Mono", Menlo, monospace',
-                  }}
-                >
-                  <Train style={{ width: 10, height: 10 }} />
-                  Railway
-                </button>
+                {PROVIDERS.map((p) => (
+                  <button
+                    key={p}
+                    type="button"
+                    onClick={() => setAddingProvider(p)}
+                    style={{
+                      padding: '6px 8px',
+                      backgroundColor: addingProvider !== p
+                        ? 'transparent'
+                        : p === 'railway' ? 'rgba(139, 92, 246, 0.2)' : 'rgba(255,255,255,0.1)',
+                      color: addingProvider !== p
+                        ? '#666'
+                        : p === 'railway' ? '#a78bfa' : '#e5e5e5',
+                      border: 'none',
+                      borderRadius: 4,
+                      cursor: 'pointer',
+                      fontSize: 10,
+                      fontWeight: 500,
+                      display: 'flex',
+                      alignItems: 'center',
+                      justifyContent: 'center',
+                      gap: 4,
+                      fontFamily: 'ui-monospace, SFMono-Regular, "SF Mono", Menlo, monospace',
+                    }}
+                  >
+                    {p === 'vercel' ? (
+                      <svg width="10" height="10" viewBox="0 0 76 65" fill="currentColor">
+                        <path d="M37.5274 0L75.0548 65H0L37.5274 0Z" />
+                      </svg>
+                    ) : p === 'railway' ? (
+                      <Train style={{ width: 10, height: 10 }} />
+                    ) : (
+                      <Cloud style={{ width: 10, height: 10 }} />
+                    )}
+                    {PROVIDER_NAMES[p]}
+                  </button>
+                ))}
               </div>
 
               {/* Railway Token Type Selector */}
@@ -456,11 +459,34 @@ export function Settings({ onBack }: Props) {
                 </div>
               )}
 
+              {/* Cloudflare Account ID */}
+              {addingProvider === 'cloudflare' && (
+                <input
+                  type="text"
+                  value={cloudflareAccountId}
+                  onChange={(e) => setCloudflareAccountId(e.target.value)}
+                  placeholder="Cloudflare account ID"
+                  disabled={isSubmitting}
+                  style={{
+                    width: '100%',
+                    padding: '10px 12px',
+                    backgroundColor: 'rgba(255,255,255,0.05)',
+                    border: '1px solid rgba(255,255,255,0.15)',
+                    borderRadius: 6,
+                    color: '#e5e5e5',
+                    fontSize: 12,
+                    fontFamily: 'ui-monospace, SFMono-Regular, "SF Mono", Menlo, monospace',
+                    outline: 'none',
+                    marginBottom: 8,
+                  }}
+                />
+              )}
+
               <input
                 type="password"
                 value={newToken}
                 onChange={(e) => setNewToken(e.target.value)}
-                placeholder={`Paste ${addingProvider === 'railway' ? 'Railway' : 'Vercel'} token here...`}
+                placeholder={`Paste ${PROVIDER_NAMES[addingProvider]} token here...`}
                 autoFocus
                 disabled={isSubmitting}
                 style={{
@@ -492,6 +518,7 @@ export function Settings({ onBack }: Props) {
                   onClick={() => {
                     setIsAddingAccount(false);
                     setNewToken('');
+                    setCloudflareAccountId('');
                     setError(null);
                   }}
                   disabled={isSubmitting}
@@ -560,7 +587,7 @@ export function Settings({ onBack }: Props) {
                 }}
               >
                 <ExternalLink style={{ width: 12, height: 12 }} />
-                Create token on {addingProvider === 'railway' ? 'Railway' : 'Vercel'}
+                Create token on {PROVIDER_NAMES[addingProvider]}
               </button>
             </form>
           ) : (
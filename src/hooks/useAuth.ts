@@ -1,7 +1,7 @@
 import { useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useStore } from '../store';
-import type { User, Account, Provider } from '../types';
+import { errorMessage, type User, type Account, type Provider } from '../types';
 
 export function useAuth() {
   const { user, isAuthenticated, isLoading, setUser, setLoading, setView, logout, currentAccount, setCurrentAccount } = useStore();
@@ -60,7 +60,7 @@ export function useAuth() {
       return { success: true };
     } catch (error) {
       setLoading(false);
-      return { success: false, error: String(error) };
+      return { success: false, error: errorMessage(error) };
     }
   }, [setUser, setLoading, setView, setCurrentAccount]);
 
@@ -29,6 +29,9 @@ function mapRailwayStatus(status: string): DeploymentState {
   }
 }
 
+// States a Vercel deployment never leaves
+const SETTLED_STATES: DeploymentState[] = ['READY', 'ERROR', 'CANCELED'];
+
 // Convert Vercel deployment to unified format
 function vercelToUnified(d: Deployment): UnifiedDeployment {
   return {
@@ -77,6 +80,9 @@ export function useDeployments() {
   } = useStore();
 
   const pollIntervalRef = useRef<number | null>(null);
+  // Latest list for incremental polls, without refetching on every change
+  const deploymentsRef = useRef(deployments);
+  deploymentsRef.current = deployments;
 
   const fetchProjects = useCallback(async () => {
     if (!isAuthenticated || !currentAccount) return;
@@ -94,7 +100,9 @@ export function useDeployments() {
     }
   }, [isAuthenticated, currentAccount, setProjects]);
 
-  const fetchDeployments = useCallback(async (projectId?: string) => {
+  // `incremental` polls only fetch newer Vercel deployments while every
+  // listed one has settled
+  const fetchDeployments = useCallback(async (projectId?: string, incremental = false) => {
     if (!isAuthenticated || !currentAccount) return;
     try {
       if (currentAccount.provider === 'railway') {
@@ -109,10 +117,15 @@ export function useDeployments() {
         // Also set legacy deployments for compatibility
         setDeployments([]);
       } else {
-        const deploymentList = await invoke<Deployment[]>('list_deployments', {
+        const current = deploymentsRef.current;
+        const settled = current.every(d => SETTLED_STATES.includes((d.state || d.readyState) as DeploymentState));
+        const since = incremental && settled ? current[0]?.createdAt ?? null : null;
+        const fetched = await invoke<Deployment[]>('list_deployments', {
           projectId: projectId || null,
           limit: 20,
+          since,
         });
+        const deploymentList = since ? [...fetched, ...current].slice(0, 20) : fetched;
         setDeployments(deploymentList);
         const unified = deploymentList.map(vercelToUnified);
         setUnifiedDeployments(unified);
@@ -155,7 +168,7 @@ export function useDeployments() {
     }
 
     pollIntervalRef.current = window.setInterval(() => {
-      fetchDeployments(selectedProjectId || undefined);
+      fetchDeployments(selectedProjectId || undefined, true);
     }, 30000); // Poll every 30 seconds
   }, [fetchDeployments, selectedProjectId]);
 
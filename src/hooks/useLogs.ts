@@ -15,22 +15,22 @@ export function useLogs() {
     setStreaming,
   } = useStore();
 
-  const fetchLogs = useCallback(async (deploymentId: string) => {
+  const fetchLogs = useCallback(async (deploymentId: string, accountId?: string | null) => {
     clearLogs();
     try {
-      const logLines = await invoke<LogLine[]>('fetch_deployment_logs', { deploymentId });
+      const logLines = await invoke<LogLine[]>('fetch_deployment_logs', { deploymentId, accountId });
       setLogs(logLines);
     } catch (error) {
       console.error('Failed to fetch logs:', error);
     }
   }, [setLogs, clearLogs]);
 
-  const startStreaming = useCallback(async (deploymentId: string) => {
+  const startStreaming = useCallback(async (deploymentId: string, accountId?: string | null) => {
     clearLogs();
     setStreaming(true);
 
     try {
-      await invoke('stream_deployment_logs', { deploymentId });
+      await invoke('stream_deployment_logs', { deploymentId, accountId });
     } catch (error) {
       console.error('Failed to start log streaming:', error);
       setStreaming(false);
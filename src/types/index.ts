@@ -1,4 +1,13 @@
-export type Provider = 'vercel' | 'railway';
+export type Provider = 'vercel' | 'railway' | 'netlify' | 'render' | 'fly' | 'cloudflare';
+
+export const PROVIDER_NAMES: Record<Provider, string> = {
+  vercel: 'Vercel',
+  railway: 'Railway',
+  netlify: 'Netlify',
+  render: 'Render',
+  fly: 'Fly.io',
+  cloudflare: 'Cloudflare',
+};
 
 export interface User {
   id: string;
@@ -15,7 +24,21 @@ export interface Account {
   scopeType: string;
   teamName: string | null;
   teamSlug: string | null;
+  teamId: string | null;
   provider: Provider;
+  // Extra Vercel teams polled with the same token
+  includedTeams: TeamScope[];
+}
+
+export interface TeamScope {
+  id: string;
+  slug: string;
+  name: string;
+}
+
+// Returned by list_vercel_teams
+export interface VercelTeamOption extends TeamScope {
+  included: boolean;
 }
 
 export interface Project {
@@ -130,6 +153,11 @@ export interface UnifiedDeployment {
   projectId?: string;
   serviceId?: string;
   environmentId?: string;
+  environment?: string | null;
+  dashboardUrl?: string | null;
+  logsUrl?: string | null;
+  queueMs?: number | null;
+  buildMs?: number | null;
 }
 
 export interface LogLine {
@@ -139,3 +167,109 @@ export interface LogLine {
 }
 
 export type View = 'auth' | 'deployments' | 'logs' | 'settings';
+
+// Error returned by every backend command
+export type AppError =
+  | { kind: 'unauthorized'; message: string }
+  | { kind: 'forbidden'; message: string }
+  | { kind: 'rateLimited'; retryAfter: number | null; message: string }
+  | { kind: 'network'; message: string }
+  | { kind: 'notFound'; message: string }
+  | { kind: 'provider'; code: string | null; message: string }
+  | { kind: 'keychain'; message: string };
+
+export function errorMessage(err: unknown): string {
+  if (err && typeof err === 'object' && 'message' in err) {
+    return String((err as { message: unknown }).message);
+  }
+  return String(err);
+}
+
+// Returned by get_rate_limits
+export interface RateLimitState {
+  limit: number | null;
+  remaining: number | null;
+  resetAt: number | null; // Unix seconds
+  limitedUntil: number | null; // Unix seconds
+}
+
+export interface AccountRateLimit {
+  accountId: string;
+  provider: Provider;
+  state: RateLimitState;
+}
+
+// Returned by get_build_stats
+export interface BuildSample {
+  deploymentId: string;
+  createdAt: number | null;
+  branch: string | null;
+  commitMessage: string | null;
+  queueMs: number | null;
+  buildMs: number;
+}
+
+export interface ProjectBuildStats {
+  provider: Provider;
+  accountId: string | null;
+  projectId: string | null;
+  name: string;
+  builds: number;
+  p50BuildMs: number;
+  p90BuildMs: number;
+  p50QueueMs: number | null;
+  trend: number | null; // Above 1 when builds are getting slower
+  recent: BuildSample[]; // Oldest first
+  slowest: BuildSample[];
+}
+
+// Returned by get_webhook_config; Vercel posts to /vercel, Railway to /railway?secret=...
+export interface WebhookConfig {
+  enabled: boolean;
+  port: number;
+  listenOnLan: boolean;
+  vercelSecret: string | null;
+  railwaySecret: string | null;
+}
+
+// Outbound Slack or Discord webhook, managed with save_integration
+export interface Integration {
+  id: string; // Empty when adding
+  kind: 'slack' | 'discord';
+  name: string;
+  webhookUrl: string;
+  enabled: boolean;
+}
+
+export type DeploymentPhase = 'QUEUED' | 'BUILDING' | 'READY' | 'ERROR' | 'CANCELED' | 'UNKNOWN';
+
+export type RuleAction =
+  | { type: 'desktop' }
+  | { type: 'sound' }
+  | { type: 'webhook'; integrationIds: string[] } // Empty for all integrations
+  | { type: 'none' };
+
+// Empty lists and null fields match anything
+export interface NotificationRule {
+  id: string; // Empty when adding
+  name: string;
+  enabled: boolean;
+  providers: Provider[];
+  accountIds: string[];
+  projects: string[]; // Project IDs or names
+  branchPattern: string | null; // `*` matches anything
+  environment: string | null;
+  from: DeploymentPhase | null;
+  to: DeploymentPhase | null;
+  actions: RuleAction[];
+}
+
+// Returned by get_notification_settings; the first matching rule wins
+export interface NotificationSettings {
+  rules: NotificationRule[];
+  quietHours: {
+    enabled: boolean;
+    start: string; // "HH:MM", local time
+    end: string;
+  };
+}